@@ -1,5 +1,10 @@
 //! Basic example showing how to use bevy_outliner.
 //!
+//! Controls:
+//! - W: cycle outline width
+//! - C: cycle outline color
+//! - Space: toggle outlines
+//!
 //! Run with: cargo run --example basic
 
 use bevy::{
@@ -23,7 +28,7 @@ fn main() {
             LogDiagnosticsPlugin::default(),
         ))
         .add_systems(Startup, setup)
-        .add_systems(Update, rotate_cubes)
+        .add_systems(Update, (rotate_cubes, change_outlines))
         .run();
 }
 
@@ -93,6 +98,45 @@ fn setup(
 #[derive(Component)]
 struct Rotates;
 
+// Starts at the 5px the cubes spawn with
+const WIDTHS: [f32; 4] = [5.0, 10.0, 20.0, 2.0];
+
+const COLORS: [OutlineColorPreset; 4] = [
+    OutlineColorPreset::Selected,
+    OutlineColorPreset::Enemy,
+    OutlineColorPreset::Ally,
+    OutlineColorPreset::Highlight,
+];
+
+/// Changes outlines at runtime from the keyboard
+fn change_outlines(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut width_index: Local<usize>,
+    mut color_index: Local<usize>,
+    mut outlines: Query<&mut MeshOutline>,
+    mut settings: Query<&mut OutlineSettings>,
+) {
+    if keys.just_pressed(KeyCode::KeyW) {
+        *width_index = (*width_index + 1) % WIDTHS.len();
+        for mut outline in outlines.iter_mut() {
+            outline.width = Vec2::splat(WIDTHS[*width_index]);
+        }
+    }
+
+    if keys.just_pressed(KeyCode::KeyC) {
+        *color_index = (*color_index + 1) % COLORS.len();
+        for mut outline in outlines.iter_mut() {
+            outline.color = COLORS[*color_index].into();
+        }
+    }
+
+    if keys.just_pressed(KeyCode::Space) {
+        for mut settings in settings.iter_mut() {
+            settings.enabled = !settings.enabled;
+        }
+    }
+}
+
 fn rotate_cubes(time: Res<Time>, mut query: Query<&mut Transform, With<Rotates>>) {
     for mut transform in query.iter_mut() {
         transform.rotate_y(time.delta_secs() * 0.5);
@@ -0,0 +1,157 @@
+//! Shows the Jump Flood distance field behind the outlines.
+//!
+//! Press Space to toggle an overlay of the raw seed field: each pixel is
+//! colored by the coordinates of its nearest silhouette pixel, so the flat
+//! regions show which object every pixel measures its outline distance to. Use the
+//! up and down arrows to change `max_width` and watch the field grow.
+//!
+//! Run with: cargo run --example distance_field
+
+use bevy::prelude::*;
+use bevy_outliner::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, OutlinePlugin))
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (toggle_overlay, adjust_max_width, show_distance_field, rotate),
+        )
+        .run();
+}
+
+/// Full-screen image showing the distance field
+#[derive(Component)]
+struct DistanceFieldOverlay;
+
+#[derive(Component)]
+struct Rotates;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let shapes = [
+        meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        meshes.add(Sphere::new(0.6)),
+        meshes.add(Torus::new(0.3, 0.6)),
+    ];
+
+    for (i, shape) in shapes.into_iter().enumerate() {
+        commands.spawn((
+            Mesh3d(shape),
+            MeshMaterial3d(materials.add(Color::srgb(0.6, 0.6, 0.7))),
+            Transform::from_xyz(i as f32 * 2.5 - 2.5, 0.6, 0.0),
+            MeshOutline::new(LinearRgba::new(1.0, 0.5, 0.0, 1.0), 12.0),
+            Rotates,
+        ));
+    }
+
+    // Ground plane
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(20.0, 20.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.3, 0.3))),
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            illuminance: 10000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Camera with outline support
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 4.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        OutlineSettings {
+            max_width: 32,
+            distance_field: true,
+            ..default()
+        },
+    ));
+
+    // Overlay, hidden until toggled. Its image is set once the outline
+    // textures exist
+    commands.spawn((
+        // Seeds store texel coordinates in 1/65535 steps, scaled up here to
+        // span a 2K view
+        ImageNode {
+            color: LinearRgba::new(32.0, 32.0, 0.0, 1.0).into(),
+            ..default()
+        },
+        Node {
+            width: percent(100),
+            height: percent(100),
+            ..default()
+        },
+        Visibility::Hidden,
+        DistanceFieldOverlay,
+    ));
+
+    commands.spawn((
+        Text::new("Space: toggle distance field\nUp/Down: change max_width"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12),
+            left: px(12),
+            ..default()
+        },
+    ));
+}
+
+fn toggle_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlays: Query<&mut Visibility, With<DistanceFieldOverlay>>,
+) {
+    if !keyboard.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for mut visibility in overlays.iter_mut() {
+        visibility.toggle_visible_hidden();
+    }
+}
+
+fn adjust_max_width(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut cameras: Query<&mut OutlineSettings>,
+) {
+    for mut settings in cameras.iter_mut() {
+        if keyboard.just_pressed(KeyCode::ArrowUp) {
+            settings.max_width = (settings.max_width * 2).max(1);
+        }
+        if keyboard.just_pressed(KeyCode::ArrowDown) {
+            settings.max_width /= 2;
+        }
+    }
+}
+
+/// Points the overlay at the Jump Flood texture holding the final field,
+/// which alternates between ping and pong as the pass count changes
+fn show_distance_field(
+    cameras: Query<(&OutlineCameraLink, &OutlineSettings)>,
+    mut overlays: Query<&mut ImageNode, With<DistanceFieldOverlay>>,
+) {
+    let Some((link, settings)) = cameras.iter().next() else {
+        return;
+    };
+    let Some(distance_field) = link.distance_field(settings) else {
+        return;
+    };
+    for mut overlay in overlays.iter_mut() {
+        if overlay.image != *distance_field {
+            overlay.image = distance_field.clone();
+        }
+    }
+}
+
+fn rotate(time: Res<Time>, mut query: Query<&mut Transform, With<Rotates>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate_y(time.delta_secs() * 0.3);
+    }
+}
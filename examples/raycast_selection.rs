@@ -0,0 +1,113 @@
+//! Click objects to toggle their outlines.
+//!
+//! Casts a ray from the cursor into the scene and adds or removes
+//! `MeshOutline` on the first selectable mesh it hits.
+//!
+//! Run with: cargo run --example raycast_selection
+
+use bevy::prelude::*;
+use bevy_outliner::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, OutlinePlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_outline_on_click)
+        .run();
+}
+
+/// Marker for meshes that can be clicked
+#[derive(Component)]
+struct Selectable;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let shapes = [
+        meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        meshes.add(Sphere::new(0.6)),
+        meshes.add(Cylinder::new(0.5, 1.2)),
+        meshes.add(Torus::new(0.3, 0.6)),
+    ];
+
+    for (i, shape) in shapes.into_iter().enumerate() {
+        commands.spawn((
+            Mesh3d(shape),
+            MeshMaterial3d(materials.add(Color::srgb(0.6, 0.6, 0.7))),
+            Transform::from_xyz(i as f32 * 2.0 - 3.0, 0.6, 0.0),
+            Selectable,
+        ));
+    }
+
+    // Ground plane
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(20.0, 20.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.3, 0.3))),
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            illuminance: 10000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Camera with outline support
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 5.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        OutlineSettings::default(),
+    ));
+
+    commands.spawn((
+        Text::new("Click an object to toggle its outline"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12),
+            left: px(12),
+            ..default()
+        },
+    ));
+}
+
+fn toggle_outline_on_click(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform), With<OutlineSettings>>,
+    selectable: Query<Has<MeshOutline>, With<Selectable>>,
+    mut ray_cast: MeshRayCast,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(cursor) = windows.iter().next().and_then(Window::cursor_position) else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.single() else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor) else {
+        return;
+    };
+
+    // Only hit selectable meshes, not the ground or silhouette copies
+    let filter = |entity| selectable.contains(entity);
+    let settings = MeshRayCastSettings::default().with_filter(&filter);
+    let Some((entity, _)) = ray_cast.cast_ray(ray, &settings).first() else {
+        return;
+    };
+
+    if selectable.get(*entity).unwrap_or(false) {
+        commands.entity(*entity).remove::<MeshOutline>();
+    } else {
+        commands
+            .entity(*entity)
+            .insert(MeshOutline::with_color(OutlineColorPreset::Selected));
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! Run with: cargo run --example with_egui --features bevy_egui
 
+#[cfg(not(feature = "bevy_egui"))]
+compile_error!("the with_egui example requires `--features bevy_egui`");
+
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts, EguiPlugin, EguiPrimaryContextPass};
 use bevy_outliner::prelude::*;
@@ -19,7 +22,8 @@ fn main() {
 #[derive(Resource)]
 struct OutlineConfig {
     color: [f32; 4],
-    width: f32,
+    /// Width slider position, mapped through `OutlineWidthCurve`
+    width_slider: f32,
     enabled: bool,
 }
 
@@ -27,7 +31,7 @@ impl Default for OutlineConfig {
     fn default() -> Self {
         Self {
             color: [1.0, 0.5, 0.0, 1.0],
-            width: 5.0,
+            width_slider: OutlineWidthCurve::default().slider_value(5.0),
             enabled: true,
         }
     }
@@ -77,7 +81,12 @@ fn setup(
 fn ui_system(mut contexts: EguiContexts, mut config: ResMut<OutlineConfig>) -> Result {
     egui::Window::new("Outline Settings").show(contexts.ctx_mut()?, |ui| {
         ui.checkbox(&mut config.enabled, "Enable Outlines");
-        ui.add(egui::Slider::new(&mut config.width, 1.0..=20.0).text("Width"));
+        let width = OutlineWidthCurve::default().width(config.width_slider);
+        ui.add(
+            egui::Slider::new(&mut config.width_slider, 0.0..=1.0)
+                .show_value(false)
+                .text(format!("Width ({width:.1}px)")),
+        );
         ui.color_edit_button_rgba_unmultiplied(&mut config.color);
     });
     Ok(())
@@ -99,7 +108,7 @@ fn update_outlines(
             config.color[2],
             config.color[3],
         );
-        outline.width = config.width;
+        outline.width = Vec2::splat(OutlineWidthCurve::default().width(config.width_slider));
     }
 
     for mut settings in camera_query.iter_mut() {
@@ -0,0 +1,116 @@
+//! Outline styles shared by categories of entities.
+//!
+//! [`OutlineCategory`] tags an entity with a category, and
+//! [`OutlineCategories`] holds the [`MeshOutline`] style of each category,
+//! so a whole category can be restyled by editing one entry.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::components::MeshOutline;
+
+/// Puts an entity in an outline category of [`OutlineCategories`].
+///
+/// The entity's [`MeshOutline`] is managed from the category's style; edit
+/// the style in [`OutlineCategories`] rather than the component. Entities
+/// whose category has no style are not outlined.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct OutlineCategory(pub u8);
+
+/// Outline style of each [`OutlineCategory`].
+///
+/// ```
+/// # use bevy_outliner::prelude::*;
+/// const ENEMY: u8 = 1;
+///
+/// let mut categories = OutlineCategories::default();
+/// categories
+///     .styles
+///     .insert(ENEMY, MeshOutline::with_color(OutlineColorPreset::Enemy));
+/// ```
+#[derive(Resource, Default)]
+pub struct OutlineCategories {
+    /// Style of each category, by category number.
+    pub styles: HashMap<u8, MeshOutline>,
+}
+
+/// System to apply category styles to [`OutlineCategory`] entities
+pub fn update_outline_categories(
+    mut commands: Commands,
+    categories: Res<OutlineCategories>,
+    entities: Query<(Entity, Ref<OutlineCategory>, Has<MeshOutline>)>,
+    mut removed: RemovedComponents<OutlineCategory>,
+    outlined: Query<(), (With<MeshOutline>, Without<OutlineCategory>)>,
+) {
+    for (entity, category, has_outline) in entities.iter() {
+        // Restyle everything when the resource changes, else only new tags
+        if !categories.is_changed() && !category.is_changed() {
+            continue;
+        }
+
+        match categories.styles.get(&category.0) {
+            Some(style) => {
+                commands.entity(entity).insert(*style);
+            }
+            None if has_outline => {
+                commands.entity(entity).remove::<MeshOutline>();
+            }
+            None => {}
+        }
+    }
+
+    // Drop the outline along with the category
+    for entity in removed.read() {
+        if outlined.contains(entity) {
+            commands.entity(entity).remove::<MeshOutline>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn category_style_is_applied_and_dropped() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<OutlineCategories>()
+            .add_systems(Update, update_outline_categories);
+        let style = MeshOutline::with_color(LinearRgba::RED);
+        app.world_mut()
+            .resource_mut::<OutlineCategories>()
+            .styles
+            .insert(1, style);
+        let entity = app.world_mut().spawn(OutlineCategory(1)).id();
+        let outline = |app: &App| app.world().get::<MeshOutline>(entity).map(|o| o.color);
+
+        app.update();
+        assert_eq!(outline(&app), Some(style.color));
+
+        // Restyling the category restyles its entities
+        let restyled = MeshOutline::with_color(LinearRgba::BLUE);
+        app.world_mut()
+            .resource_mut::<OutlineCategories>()
+            .styles
+            .insert(1, restyled);
+        app.update();
+        assert_eq!(outline(&app), Some(restyled.color));
+
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(OutlineCategory(2));
+        app.update();
+        assert_eq!(outline(&app), None);
+
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(OutlineCategory(1));
+        app.update();
+        app.world_mut()
+            .entity_mut(entity)
+            .remove::<OutlineCategory>();
+        app.update();
+        assert_eq!(outline(&app), None);
+    }
+}
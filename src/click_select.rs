@@ -0,0 +1,62 @@
+//! Click-to-select outlines through `bevy_picking`.
+//!
+//! Clicking an entity with [`OutlineClickSelect`] toggles its
+//! [`OutlineSelected`] marker, and selected entities are outlined with the
+//! component's style. Needs a picking backend that hits meshes, such as
+//! `MeshPickingPlugin`.
+
+use bevy::prelude::*;
+
+use crate::components::MeshOutline;
+
+/// Lets clicks on this entity toggle [`OutlineSelected`].
+#[derive(Component, Clone, Copy, Default)]
+pub struct OutlineClickSelect {
+    /// Outline shown while the entity is selected.
+    pub style: MeshOutline,
+}
+
+/// Marks an [`OutlineClickSelect`] entity as selected.
+///
+/// Toggled by clicks, but can also be inserted or removed directly. Its
+/// [`MeshOutline`] is added and removed along with it.
+#[derive(Component, Clone, Copy, Default)]
+pub struct OutlineSelected;
+
+/// Observer toggling [`OutlineSelected`] on clicked selectable entities
+pub fn toggle_selected_on_click(
+    click: On<Pointer<Click>>,
+    mut commands: Commands,
+    selectable: Query<Has<OutlineSelected>, With<OutlineClickSelect>>,
+) {
+    let Ok(selected) = selectable.get(click.entity) else {
+        return;
+    };
+    if selected {
+        commands.entity(click.entity).remove::<OutlineSelected>();
+    } else {
+        commands.entity(click.entity).insert(OutlineSelected);
+    }
+}
+
+/// Observer outlining entities as they are selected
+pub fn outline_selected(
+    add: On<Add, OutlineSelected>,
+    mut commands: Commands,
+    selectable: Query<&OutlineClickSelect>,
+) {
+    if let Ok(select) = selectable.get(add.entity) {
+        commands.entity(add.entity).insert(select.style);
+    }
+}
+
+/// Observer removing the outline of deselected entities
+pub fn unoutline_deselected(
+    remove: On<Remove, OutlineSelected>,
+    mut commands: Commands,
+    selectable: Query<(), With<OutlineClickSelect>>,
+) {
+    if selectable.contains(remove.entity) {
+        commands.entity(remove.entity).try_remove::<MeshOutline>();
+    }
+}
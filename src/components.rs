@@ -1,32 +1,70 @@
-use bevy::{prelude::*, render::extract_component::ExtractComponent};
+use bevy::{prelude::*, render::extract_component::ExtractComponent, shader::Shader};
 
 /// Component that marks an entity to be outlined.
 ///
 /// Add this component to any entity with a mesh to give it an outline.
-#[derive(Component, Clone, Copy, ExtractComponent, Reflect)]
+///
+/// Only the [`Mesh3d`] is needed: silhouettes are drawn with their own
+/// material, so entities without a `MeshMaterial3d`, which the main camera
+/// doesn't draw, still get an outline around the empty space they occupy.
+#[derive(Component, Clone, Copy, PartialEq, ExtractComponent, Reflect)]
 #[reflect(Component)]
 pub struct MeshOutline {
     /// The color of the outline.
     pub color: LinearRgba,
-    /// The width of the outline in pixels.
-    pub width: f32,
+    /// The horizontal and vertical width of the outline, measured in
+    /// [`width_unit`](Self::width_unit). Different widths stretch the
+    /// outline into an elliptical halo, e.g. `Vec2::new(8.0, 2.0)` for a
+    /// wide, flat outline.
+    pub width: Vec2,
+    /// The unit [`width`](Self::width) is measured in.
+    pub width_unit: WidthUnit,
+    /// Draw the outline as dashes instead of a solid line.
+    pub dash: Option<OutlineDash>,
+    /// Shape the outline is drawn around.
+    pub shape: OutlineShape,
+    /// Take the outline color from the mesh's vertex colors nearest to each
+    /// outline pixel, keeping only the alpha of [`color`](Self::color). The
+    /// vertex colors are quantized to a few bits per channel.
+    pub vertex_colors: bool,
+    /// Tint drawn over the object's interior, blended by its alpha.
+    pub fill: Option<LinearRgba>,
+    /// Draw the interior as hatch lines in the [`fill`](Self::fill) color,
+    /// or the outline color when there is no fill.
+    pub hatch: Option<OutlineHatch>,
+    /// Draw the outline as a soft halo fading out from the silhouette edge,
+    /// as if the object were backlit, instead of a solid line.
+    ///
+    /// On cameras with a single-sampled
+    /// [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass), scene
+    /// geometry nearer than the object's edge covers the halo, so it only
+    /// shows behind the object. Without one the halo is drawn over everything.
+    pub backlight: bool,
 }
 
 impl Default for MeshOutline {
     fn default() -> Self {
         Self {
             color: LinearRgba::new(1.0, 0.5, 0.0, 1.0),
-            width: 5.0,
+            width: Vec2::splat(5.0),
+            width_unit: WidthUnit::Pixels,
+            dash: None,
+            shape: OutlineShape::Mesh,
+            vertex_colors: false,
+            fill: None,
+            hatch: None,
+            backlight: false,
         }
     }
 }
 
 impl MeshOutline {
-    /// Create a new outline with the specified color and width.
+    /// Create a new outline with the specified color and uniform width.
     pub fn new(color: impl Into<LinearRgba>, width: f32) -> Self {
         Self {
             color: color.into(),
-            width,
+            width: Vec2::splat(width),
+            ..Default::default()
         }
     }
 
@@ -38,28 +76,625 @@ impl MeshOutline {
         }
     }
 
-    /// Create an outline with default color and the specified width.
+    /// Create an outline with default color and the specified uniform width.
     pub fn with_width(width: f32) -> Self {
         Self {
-            width,
+            width: Vec2::splat(width),
             ..Default::default()
         }
     }
 }
 
+/// Dash pattern of a [`MeshOutline`].
+///
+/// Dashes run along the outlined object's contour, each object with its own
+/// pattern. A non-zero [`speed`](Self::speed) scrolls them over time, so they
+/// march around the object like a marquee selection. The pattern restarts at
+/// sharp corners of the silhouette.
+#[derive(Clone, Copy, PartialEq, Reflect)]
+pub struct OutlineDash {
+    /// Length of each dash in pixels.
+    pub length: f32,
+    /// Length of the gap between dashes in pixels.
+    pub gap: f32,
+    /// Scroll speed of the pattern in pixels per second along the contour.
+    pub speed: f32,
+}
+
+impl Default for OutlineDash {
+    fn default() -> Self {
+        Self {
+            length: 6.0,
+            gap: 4.0,
+            speed: 0.0,
+        }
+    }
+}
+
+impl OutlineDash {
+    /// Classic "marching ants" selection dashes.
+    pub fn marching_ants() -> Self {
+        Self {
+            length: 4.0,
+            gap: 4.0,
+            speed: 16.0,
+        }
+    }
+}
+
+/// Simplified mesh drawn as an entity's silhouette instead of its own mesh.
+///
+/// Silhouettes redraw the outlined mesh on a separate camera, doubling the
+/// cost of dense meshes. A decimated proxy is usually indistinguishable in
+/// the outline and much cheaper to draw. It should cover the same screen
+/// area as the full mesh, or the outline drifts from the visible edges.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct SilhouetteMeshOverride(pub Handle<Mesh>);
+
+/// Custom vertex shader for an entity's silhouette.
+///
+/// Silhouettes go through Bevy's mesh vertex shader, which doesn't know
+/// about deformation applied by the entity's own material, such as blend
+/// shape offsets sampled from a texture. This shader replaces it, so the
+/// silhouette deforms like the visible mesh.
+///
+/// The shader must output `bevy_pbr::forward_io::VertexOutput`, like Bevy's
+/// `mesh.wgsl` it can start from. [`texture`](Self::texture) is bound at
+/// `@group(#{MATERIAL_BIND_GROUP}) @binding(3)` with its sampler at
+/// `@binding(4)`.
+#[derive(Component, Clone, Debug, Default)]
+pub struct OutlineVertexShader {
+    /// Vertex shader replacing Bevy's mesh vertex shader.
+    pub shader: Handle<Shader>,
+    /// Texture the shader reads its deformation from.
+    pub texture: Option<Handle<Image>>,
+}
+
+/// Hatch pattern filling the interior of a [`MeshOutline`].
+///
+/// Lines are laid out in screen space, so the pattern stays put as the
+/// object moves, like a technical drawing.
+#[derive(Clone, Copy, PartialEq, Reflect)]
+pub struct OutlineHatch {
+    /// Distance between lines in pixels.
+    pub spacing: f32,
+    /// Direction of the lines in radians, counterclockwise from horizontal.
+    pub angle: f32,
+    /// Thickness of each line in pixels.
+    pub line_width: f32,
+}
+
+impl Default for OutlineHatch {
+    fn default() -> Self {
+        Self {
+            spacing: 8.0,
+            angle: std::f32::consts::FRAC_PI_4,
+            line_width: 1.5,
+        }
+    }
+}
+
+/// Shape a [`MeshOutline`] is drawn around.
+#[derive(Clone, Copy, Default, PartialEq, Reflect)]
+pub enum OutlineShape {
+    /// The exact silhouette of the entity's mesh.
+    #[default]
+    Mesh,
+    /// The entity's bounding box. Much cheaper to render than a detailed
+    /// mesh, and good enough for debug views and coarse selection.
+    BoundingBox,
+}
+
+/// Custom fragment shader for an entity's silhouette.
+///
+/// Replaces the built-in silhouette shader, so objects can shape their own
+/// outline, e.g. by discarding fragments with a procedural mask, or change
+/// the coverage they write. Entities without one keep the built-in shader.
+///
+/// The shader must output the silhouette encoding described in
+/// [`OutlineCameraLink::silhouette_texture`](crate::OutlineCameraLink::silhouette_texture),
+/// which the built-in `silhouette.wgsl` it can start from produces. The
+/// material uniform and alpha texture are bound at
+/// `@group(#{MATERIAL_BIND_GROUP})` bindings 0 to 2, like in that shader.
+#[derive(Component, Clone, Debug, Default)]
+pub struct OutlineFragmentShader(pub Handle<Shader>);
+
+/// Unit of [`MeshOutline::width`].
+#[derive(Clone, Copy, Default, PartialEq, Reflect)]
+pub enum WidthUnit {
+    /// Width in pixels of the outline texture.
+    #[default]
+    Pixels,
+    /// Width as a fraction of the viewport height, so outlines keep their
+    /// size relative to the screen at any resolution. `0.01` is 10.8 pixels
+    /// at 1080p and 21.6 pixels at 4K.
+    ViewportFraction,
+    /// Width in logical pixels, scaled by the target's DPI scale factor.
+    ///
+    /// The result is rounded to whole physical pixels, so at fractional
+    /// scale factors such as 1.25 every edge of the outline is equally
+    /// thick instead of some edges gaining a partial pixel.
+    LogicalPixels,
+}
+
+impl WidthUnit {
+    /// Converts `width` in this unit to pixels for a target `height` pixels
+    /// tall with the given DPI `scale_factor`.
+    pub fn to_pixels(self, width: Vec2, height: u32, scale_factor: f32) -> Vec2 {
+        match self {
+            WidthUnit::Pixels => width,
+            WidthUnit::ViewportFraction => width * height as f32,
+            WidthUnit::LogicalPixels => (width * scale_factor).round(),
+        }
+    }
+}
+
+/// Maps a normalized `0..=1` slider value to an outline width.
+///
+/// A linear slider spends most of its travel on wide outlines, where a
+/// pixel more or less is barely visible. Raising the slider value to
+/// [`gamma`](Self::gamma) gives thin widths more travel instead.
+///
+/// ```
+/// # use bevy_outliner::prelude::*;
+/// let curve = OutlineWidthCurve::default();
+/// assert_eq!(curve.width(0.0), 1.0);
+/// assert_eq!(curve.width(1.0), 20.0);
+/// assert!(curve.width(0.5) < 10.5);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct OutlineWidthCurve {
+    /// Width at slider value `0`.
+    pub min: f32,
+    /// Width at slider value `1`.
+    pub max: f32,
+    /// Exponent applied to the slider value. `1.0` is linear, larger values
+    /// give thin widths more of the slider.
+    pub gamma: f32,
+}
+
+impl Default for OutlineWidthCurve {
+    fn default() -> Self {
+        Self {
+            min: 1.0,
+            max: 20.0,
+            gamma: 2.0,
+        }
+    }
+}
+
+impl OutlineWidthCurve {
+    /// Width for slider value `t`, clamped to `0..=1`.
+    pub fn width(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0).powf(self.gamma.max(f32::EPSILON));
+        self.min + (self.max - self.min) * t
+    }
+
+    /// Slider value producing `width`, the inverse of [`width`](Self::width).
+    pub fn slider_value(&self, width: f32) -> f32 {
+        let range = self.max - self.min;
+        if range == 0.0 {
+            return 0.0;
+        }
+        ((width - self.min) / range)
+            .clamp(0.0, 1.0)
+            .powf(1.0 / self.gamma.max(f32::EPSILON))
+    }
+}
+
+/// Largest [`OutlineSettings::max_width`] accepted before it is clamped.
+///
+/// Every doubling of `max_width` adds a full-screen Jump Flood pass, and
+/// wider outlines are rarely intended.
+pub const MAX_OUTLINE_WIDTH: u32 = 256;
+
 /// Camera component that enables and configures outline rendering.
 ///
 /// Add this to cameras that should render outlines.
+///
+/// Cameras with [`CameraOutputMode::Skip`](bevy::camera::CameraOutputMode::Skip)
+/// are supported: the outline is composited into the camera's main texture
+/// like any other post-process, and a later camera sharing the target
+/// writes it out.
 #[derive(Component, Clone, Copy, ExtractComponent, Reflect)]
 #[reflect(Component)]
 pub struct OutlineSettings {
+    /// Maximum outline width in pixels the Jump Flood passes can reach.
+    /// Wider outlines are clamped to this value. This is only a cap: the
+    /// passes each frame just reach the widest outline in view.
+    ///
+    /// Values above [`MAX_OUTLINE_WIDTH`] or the smaller dimension of the
+    /// render target are clamped with a warning.
+    ///
+    /// Can be changed at runtime: the Jump Flood passes are rebuilt for the
+    /// new pass count in the same frame, and only overscan textures are
+    /// resized.
+    pub max_width: u32,
     /// Whether outline rendering is enabled.
+    ///
+    /// A camera spawned disabled allocates no silhouette camera or textures
+    /// until it is first enabled. Disabling it later keeps them, so
+    /// toggling stays cheap.
     pub enabled: bool,
+    /// Automatically darken or lighten the outline when it lacks contrast
+    /// with the scene behind it, keeping it readable over both bright and
+    /// dark backgrounds.
+    pub auto_contrast: bool,
+    /// Distance (in world units) from the camera near plane over which the
+    /// outline fades in. Objects clipped by the near plane get a soft fade
+    /// instead of an abrupt cutoff. Set to `0.0` to disable.
+    pub near_fade_distance: f32,
+    /// Opacity of an ordered-dither pattern applied to outlines: the
+    /// fraction of outline pixels kept, from `0.0` keeping none to `1.0`
+    /// keeping all. The pattern is offset per object so overlapping outlines
+    /// stay individually legible. `None` disables dithering.
+    pub dither_opacity: Option<f32>,
+    /// How outlines are computed for this camera.
+    pub mode: OutlineMode,
+    /// Render silhouettes [`max_width`](Self::max_width) pixels beyond each
+    /// edge of the view, so objects partly off-screen keep their outline up
+    /// to the screen edge instead of ending where the silhouette is clipped.
+    pub overscan: bool,
+    /// Where in the post-processing chain the outline is drawn.
+    pub stage: OutlineStage,
+    /// Fraction of the previous frame's outline blended into the current
+    /// one, reducing edge shimmer on slowly moving objects. Blending backs
+    /// off where the outline changed a lot, so fast motion doesn't ghost.
+    /// Set to `0.0` to disable, which also frees the two view-sized history
+    /// textures smoothing needs.
+    pub temporal_smoothing: f32,
+    /// Region of the view outlines are confined to, in fractions of the view
+    /// size from its top left corner. Outlines are cut off at its edges, e.g.
+    /// to keep them out of a HUD at the bottom of the screen. `None` draws
+    /// outlines over the whole view.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_outliner::prelude::*;
+    /// // Leave the bottom fifth of the screen to the HUD
+    /// let settings = OutlineSettings {
+    ///     region: Some(Rect::new(0.0, 0.0, 1.0, 0.8)),
+    ///     ..default()
+    /// };
+    /// ```
+    pub region: Option<Rect>,
+    /// Leave the scene's alpha channel untouched, drawing outlines and fills
+    /// into the color channels only. Pixels without an outline always keep
+    /// their alpha; this also keeps it under outlines, for cameras whose
+    /// output is blended later by its own coverage.
+    pub preserve_alpha: bool,
+    /// Blend outlines and fills into the scene's alpha with the premultiplied
+    /// "over" operator, adding their coverage to it. Frames rendered with a
+    /// transparent clear color, e.g. for video capture, then composite
+    /// correctly over black. When disabled the alpha is blended towards the
+    /// outline color's alpha like the color channels, and fills leave it
+    /// untouched.
+    pub premultiplied_alpha: bool,
+    /// Step sizes of the Jump Flood passes, trading a pass or two for fewer
+    /// artifacts.
+    pub jfa_schedule: JfaSchedule,
+    /// Run the Jump Flood to the full [`max_width`](Self::max_width) every
+    /// frame, for effects reading the field through
+    /// [`OutlineCameraLink::distance_field`](crate::OutlineCameraLink::distance_field).
+    /// When disabled the passes only reach the widest outline in view.
+    pub distance_field: bool,
+    /// Draw outlines and fills over the view. When disabled the view is left
+    /// untouched and they are drawn onto a transparent texture instead, for
+    /// custom post-processing stacks to blend themselves: see
+    /// [`OutlineCameraLink::outline_texture`](crate::OutlineCameraLink::outline_texture).
+    /// Only applies to [`OutlineMode::Silhouette`].
+    pub auto_composite: bool,
 }
 
 impl Default for OutlineSettings {
     fn default() -> Self {
-        Self { enabled: true }
+        Self {
+            max_width: 64,
+            enabled: true,
+            auto_contrast: false,
+            near_fade_distance: 0.0,
+            dither_opacity: None,
+            mode: OutlineMode::default(),
+            overscan: false,
+            stage: OutlineStage::default(),
+            temporal_smoothing: 0.0,
+            region: None,
+            preserve_alpha: false,
+            premultiplied_alpha: false,
+            jfa_schedule: JfaSchedule::default(),
+            distance_field: false,
+            auto_composite: true,
+        }
     }
 }
 
+impl OutlineSettings {
+    /// Create settings with default values, to be configured with the
+    /// chainable setters.
+    ///
+    /// ```
+    /// # use bevy_outliner::prelude::*;
+    /// let settings = OutlineSettings::new().max_width(128).auto_contrast(true);
+    /// assert_eq!(settings.max_width, 128);
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`max_width`](Self::max_width).
+    pub fn max_width(mut self, max_width: u32) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Set [`enabled`](Self::enabled).
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set [`auto_contrast`](Self::auto_contrast).
+    pub fn auto_contrast(mut self, auto_contrast: bool) -> Self {
+        self.auto_contrast = auto_contrast;
+        self
+    }
+
+    /// Set [`near_fade_distance`](Self::near_fade_distance).
+    pub fn near_fade_distance(mut self, near_fade_distance: f32) -> Self {
+        self.near_fade_distance = near_fade_distance;
+        self
+    }
+
+    /// Enable dithering with [`dither_opacity`](Self::dither_opacity).
+    pub fn dither_opacity(mut self, dither_opacity: f32) -> Self {
+        self.dither_opacity = Some(dither_opacity);
+        self
+    }
+
+    /// Set [`mode`](Self::mode).
+    pub fn mode(mut self, mode: OutlineMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set [`overscan`](Self::overscan).
+    pub fn overscan(mut self, overscan: bool) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// Set [`stage`](Self::stage).
+    pub fn stage(mut self, stage: OutlineStage) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    /// Set [`temporal_smoothing`](Self::temporal_smoothing).
+    pub fn temporal_smoothing(mut self, temporal_smoothing: f32) -> Self {
+        self.temporal_smoothing = temporal_smoothing;
+        self
+    }
+
+    /// Set [`preserve_alpha`](Self::preserve_alpha).
+    pub fn preserve_alpha(mut self, preserve_alpha: bool) -> Self {
+        self.preserve_alpha = preserve_alpha;
+        self
+    }
+
+    /// Set [`premultiplied_alpha`](Self::premultiplied_alpha).
+    pub fn premultiplied_alpha(mut self, premultiplied_alpha: bool) -> Self {
+        self.premultiplied_alpha = premultiplied_alpha;
+        self
+    }
+
+    /// Set [`jfa_schedule`](Self::jfa_schedule).
+    pub fn jfa_schedule(mut self, jfa_schedule: JfaSchedule) -> Self {
+        self.jfa_schedule = jfa_schedule;
+        self
+    }
+
+    /// Set [`distance_field`](Self::distance_field).
+    pub fn distance_field(mut self, distance_field: bool) -> Self {
+        self.distance_field = distance_field;
+        self
+    }
+
+    /// Set [`auto_composite`](Self::auto_composite).
+    pub fn auto_composite(mut self, auto_composite: bool) -> Self {
+        self.auto_composite = auto_composite;
+        self
+    }
+
+    /// Number of Jump Flood step passes needed to reach
+    /// [`max_width`](Self::max_width) with the [`jfa_schedule`](Self::jfa_schedule).
+    pub fn jfa_pass_count(&self) -> u32 {
+        self.jfa_schedule.step_sizes(self.max_width).len() as u32
+    }
+
+    /// Whether this camera currently draws silhouette outlines, and so needs
+    /// silhouette copies of outlined entities.
+    pub(crate) fn renders_silhouettes(&self) -> bool {
+        self.enabled && self.mode == OutlineMode::Silhouette
+    }
+
+    /// Extra pixels the outline textures extend past each edge of the view.
+    pub fn overscan_margin(&self) -> u32 {
+        if self.overscan {
+            self.max_width
+        } else {
+            0
+        }
+    }
+}
+
+/// Number of Jump Flood step passes needed to propagate seeds `max_width`
+/// pixels, or `0` when `max_width` is `0`. Widths above
+/// [`MAX_OUTLINE_WIDTH`] are clamped to it.
+///
+/// This is the single source of truth for the pass count, shared by
+/// [`OutlineSettings::jfa_pass_count`] and the render node.
+///
+/// ```
+/// use bevy_outliner::{jfa_pass_count, jfa_step_size, OutlineSettings, MAX_OUTLINE_WIDTH};
+///
+/// assert_eq!(jfa_pass_count(0), 0);
+/// assert_eq!(jfa_pass_count(u32::MAX), jfa_pass_count(MAX_OUTLINE_WIDTH));
+/// for max_width in 1..=MAX_OUTLINE_WIDTH {
+///     let settings = OutlineSettings { max_width, ..Default::default() };
+///     let passes = settings.jfa_pass_count();
+///     assert_eq!(passes, jfa_pass_count(max_width));
+///     // The step sizes must cover the full width
+///     let reach: u32 = (0..passes).map(|i| jfa_step_size(max_width, i)).sum();
+///     assert!(reach >= max_width, "{max_width} px needs more than {passes} passes");
+/// }
+/// ```
+pub fn jfa_pass_count(max_width: u32) -> u32 {
+    if max_width == 0 {
+        return 0;
+    }
+    // Steps halve from a power of two down to 1, so n passes reach 2^n - 1 pixels
+    (max_width.min(MAX_OUTLINE_WIDTH) + 1).next_power_of_two().trailing_zeros()
+}
+
+/// Jump distance in pixels of Jump Flood step pass `pass_idx` out of
+/// [`jfa_pass_count`]`(max_width)`.
+pub fn jfa_step_size(max_width: u32, pass_idx: u32) -> u32 {
+    ((max_width.min(MAX_OUTLINE_WIDTH) + 1).next_power_of_two() >> (pass_idx + 1)).max(1)
+}
+
+/// Order of Jump Flood step sizes.
+///
+/// The standard schedule halves the step from half the reach down to 1.
+/// Seeds can get stranded on the way, leaving small islands of wrong
+/// nearest seeds, visible as notches at sharp corners. The variants add
+/// small steps that repair most of them.
+///
+/// ```
+/// # use bevy_outliner::JfaSchedule;
+/// assert_eq!(JfaSchedule::Standard.step_sizes(8), vec![8, 4, 2, 1]);
+/// assert_eq!(JfaSchedule::PlusTwo.step_sizes(8), vec![8, 4, 2, 1, 2, 1]);
+/// assert_eq!(JfaSchedule::OnePlus.step_sizes(8), vec![1, 8, 4, 2, 1]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum JfaSchedule {
+    /// Halving steps only, the fewest passes.
+    #[default]
+    Standard,
+    /// "1+JFA": an extra step of 1 first, spreading seeds to their
+    /// neighbours before the long jumps.
+    OnePlus,
+    /// "JFA+2": extra steps of 2 and 1 at the end, fixing most remaining
+    /// corner artifacts.
+    PlusTwo,
+}
+
+impl JfaSchedule {
+    /// Jump distances in pixels of each step pass reaching `max_width`.
+    pub fn step_sizes(self, max_width: u32) -> Vec<u32> {
+        if max_width == 0 {
+            return Vec::new();
+        }
+        let standard = (0..jfa_pass_count(max_width)).map(|pass| jfa_step_size(max_width, pass));
+        match self {
+            JfaSchedule::Standard => standard.collect(),
+            JfaSchedule::OnePlus => std::iter::once(1).chain(standard).collect(),
+            JfaSchedule::PlusTwo => standard.chain([2, 1]).collect(),
+        }
+    }
+}
+
+/// Where a camera draws its outlines relative to post-processing.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum OutlineStage {
+    /// Draw outlines after tonemapping, keeping their exact color.
+    ///
+    /// The tonemapping node is part of the 3D render graph even for cameras
+    /// with `Tonemapping::None`, where it simply does nothing, so outlines
+    /// are ordered the same with tonemapping off.
+    #[default]
+    AfterTonemapping,
+    /// Draw outlines before tonemapping, so the camera's tonemapping and
+    /// `ColorGrading` apply to them like the rest of the scene.
+    ///
+    /// Only [`Hdr`](bevy::render::view::Hdr) cameras have a separate
+    /// tonemapping pass; other cameras draw the outline as with
+    /// [`AfterTonemapping`](Self::AfterTonemapping).
+    BeforeTonemapping,
+    /// Draw outlines between the opaque and transparent passes, so glass,
+    /// water and other transparent objects are drawn over them.
+    ///
+    /// The transparent pass resolves multisampled rendering over the
+    /// outline, so this requires [`Msaa::Off`] on the camera. Multisampled
+    /// cameras draw the outline as with
+    /// [`BeforeTonemapping`](Self::BeforeTonemapping) instead.
+    ///
+    /// Deferred cameras light the scene before the opaque pass ends, so
+    /// outlines drawn here still land on the lit image.
+    BeforeTransparent,
+}
+
+impl OutlineStage {
+    /// All stages, in declaration order.
+    pub const ALL: [Self; 3] = [
+        Self::AfterTonemapping,
+        Self::BeforeTonemapping,
+        Self::BeforeTransparent,
+    ];
+}
+
+/// How a camera computes its outlines.
+#[derive(Clone, Copy, Default, PartialEq, Reflect)]
+pub enum OutlineMode {
+    /// Outline entities with [`MeshOutline`] using silhouette copies and the
+    /// Jump Flood Algorithm.
+    #[default]
+    Silhouette,
+    /// Outline every depth or normal discontinuity seen by the camera, using
+    /// its depth and normal prepasses. No silhouette camera or silhouette
+    /// meshes are spawned, and [`MeshOutline`] is not needed.
+    ///
+    /// The camera gets [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass),
+    /// [`NormalPrepass`](bevy::core_pipeline::prepass::NormalPrepass) and
+    /// [`Msaa::Off`] inserted automatically, and gets back its own prepasses
+    /// and MSAA setting when switched back to
+    /// [`Silhouette`](Self::Silhouette).
+    DepthEdges(DepthEdgeSettings),
+}
+
+/// Configuration for [`OutlineMode::DepthEdges`].
+#[derive(Clone, Copy, PartialEq, Reflect)]
+pub struct DepthEdgeSettings {
+    /// The color of the edges.
+    pub color: LinearRgba,
+    /// The width of the edges in pixels.
+    pub width: f32,
+    /// Relative depth difference between neighbouring pixels that counts as an edge.
+    pub depth_threshold: f32,
+    /// Normal difference (`1 - cos(angle)`) between neighbouring pixels that
+    /// counts as a crease.
+    pub normal_threshold: f32,
+}
+
+impl Default for DepthEdgeSettings {
+    fn default() -> Self {
+        Self {
+            color: LinearRgba::BLACK,
+            width: 1.0,
+            depth_threshold: 0.05,
+            normal_threshold: 0.4,
+        }
+    }
+}
+
+/// Overrides the size of a camera's outline textures.
+///
+/// By default the silhouette and JFA textures match the camera's render
+/// target. Add this to cameras rendering to targets whose size can't be
+/// detected automatically, or to force a specific resolution. Outline widths
+/// are measured in texels of these textures.
+///
+/// The size should keep the aspect ratio of the camera's viewport, since the
+/// silhouette camera derives its projection aspect from this size.
+#[derive(Component, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct OutlineTargetSize(pub UVec2);
@@ -0,0 +1,139 @@
+//! Emissive-driven outline brightness.
+//!
+//! [`OutlineEmissiveResponse`] reads the emissive color of an entity's
+//! [`StandardMaterial`] and brightens its [`MeshOutline`] to match, so
+//! glowing objects get glowing outlines.
+
+use bevy::{color::Luminance, prelude::*};
+
+use crate::components::MeshOutline;
+
+/// Brightens an entity's outline with its material's emissive color.
+///
+/// The outline color is multiplied by `1 + strength * luminance(emissive)`.
+/// Values above 1 only show as brighter with an HDR camera and
+/// [`OutlineStage::BeforeTonemapping`](crate::OutlineStage::BeforeTonemapping),
+/// where they can also bloom.
+///
+/// Don't combine with [`OutlineMotionEmphasis`](crate::OutlineMotionEmphasis)
+/// on the same entity, as both rewrite the outline color.
+#[derive(Component, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct OutlineEmissiveResponse {
+    /// How much the emissive luminance brightens the outline.
+    pub strength: f32,
+}
+
+impl Default for OutlineEmissiveResponse {
+    fn default() -> Self {
+        Self { strength: 1.0 }
+    }
+}
+
+/// Per-entity state of [`OutlineEmissiveResponse`]
+#[derive(Component)]
+pub struct OutlineEmissiveState {
+    /// Outline color set by the user, before brightening
+    base_color: LinearRgba,
+    /// Color last written to the outline, to notice user changes
+    written_color: LinearRgba,
+}
+
+/// System to scale [`MeshOutline`] color with the material's emissive
+pub fn update_outline_emissive(
+    mut commands: Commands,
+    materials: Res<Assets<StandardMaterial>>,
+    mut entities: Query<(
+        Entity,
+        &OutlineEmissiveResponse,
+        &MeshMaterial3d<StandardMaterial>,
+        &mut MeshOutline,
+        Option<&mut OutlineEmissiveState>,
+    )>,
+    mut removed: RemovedComponents<OutlineEmissiveResponse>,
+    mut states: Query<(&OutlineEmissiveState, &mut MeshOutline), Without<OutlineEmissiveResponse>>,
+) {
+    for (entity, response, material, mut outline, state) in entities.iter_mut() {
+        let Some(mut state) = state else {
+            commands.entity(entity).insert(OutlineEmissiveState {
+                base_color: outline.color,
+                written_color: outline.color,
+            });
+            continue;
+        };
+
+        // The user changed the color since we last wrote it
+        if outline.color != state.written_color {
+            state.base_color = outline.color;
+        }
+
+        let emissive = materials
+            .get(&material.0)
+            .map_or(0.0, |material| material.emissive.luminance());
+        let scale = 1.0 + response.strength * emissive.max(0.0);
+        let base = state.base_color;
+        let color = LinearRgba::new(
+            base.red * scale,
+            base.green * scale,
+            base.blue * scale,
+            base.alpha,
+        );
+
+        // Only write when it changed, so change detection stays quiet
+        if outline.color != color {
+            outline.color = color;
+        }
+        state.written_color = color;
+    }
+
+    // Restore the user's color when the response is removed
+    for entity in removed.read() {
+        if let Ok((state, mut outline)) = states.get_mut(entity) {
+            if outline.color == state.written_color {
+                outline.color = state.base_color;
+            }
+            commands.entity(entity).remove::<OutlineEmissiveState>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emissive_brightens_outline_until_removed() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Assets<StandardMaterial>>()
+            .add_systems(Update, update_outline_emissive);
+        let material = app
+            .world_mut()
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                emissive: LinearRgba::WHITE,
+                ..default()
+            });
+        let base = LinearRgba::new(0.5, 0.25, 0.0, 1.0);
+        let entity = app
+            .world_mut()
+            .spawn((
+                OutlineEmissiveResponse::default(),
+                MeshMaterial3d(material),
+                MeshOutline::with_color(base),
+            ))
+            .id();
+        let color = |app: &App| app.world().get::<MeshOutline>(entity).unwrap().color;
+
+        app.update();
+        app.update();
+        // Doubled by a white emissive at full strength
+        assert_eq!(color(&app), LinearRgba::new(1.0, 0.5, 0.0, 1.0));
+
+        app.world_mut()
+            .entity_mut(entity)
+            .remove::<OutlineEmissiveResponse>();
+        app.update();
+        assert_eq!(color(&app), base);
+    }
+}
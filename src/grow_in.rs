@@ -0,0 +1,159 @@
+//! Grow-in animation for new outlines.
+//!
+//! Entities with [`OutlineGrowIn`] animate their outline width from zero
+//! whenever a [`MeshOutline`] is added, instead of popping in at full width.
+
+use bevy::prelude::*;
+
+use crate::components::MeshOutline;
+
+/// Animates an entity's outline from zero to its full width each time a
+/// [`MeshOutline`] is added to it.
+#[derive(Component, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct OutlineGrowIn {
+    /// Duration of the animation in seconds.
+    pub duration: f32,
+}
+
+impl Default for OutlineGrowIn {
+    fn default() -> Self {
+        Self { duration: 0.2 }
+    }
+}
+
+/// Progress of a running [`OutlineGrowIn`] animation
+#[derive(Component)]
+pub struct OutlineGrowInState {
+    /// Width the outline grows to
+    target_width: Vec2,
+    /// Width last written to the outline, to notice user changes
+    written_width: Vec2,
+    elapsed: f32,
+}
+
+/// System to start and advance [`OutlineGrowIn`] animations
+pub fn update_outline_grow_in(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut added: Query<
+        (Entity, &mut MeshOutline),
+        (
+            With<OutlineGrowIn>,
+            Added<MeshOutline>,
+            Without<OutlineGrowInState>,
+        ),
+    >,
+    mut growing: Query<(Entity, &OutlineGrowIn, &mut MeshOutline, &mut OutlineGrowInState)>,
+) {
+    for (entity, grow_in, mut outline, mut state) in growing.iter_mut() {
+        // Re-added while still growing, start over
+        if outline.is_added() {
+            state.target_width = outline.width;
+            state.written_width = outline.width;
+            state.elapsed = 0.0;
+        }
+
+        // The user changed the width mid-animation, stop animating
+        if outline.width != state.written_width {
+            commands.entity(entity).remove::<OutlineGrowInState>();
+            continue;
+        }
+
+        state.elapsed += time.delta_secs();
+        let t = if grow_in.duration > 0.0 {
+            (state.elapsed / grow_in.duration).min(1.0)
+        } else {
+            1.0
+        };
+
+        // Ease out so the outline pops quickly and settles gently
+        let eased = 1.0 - (1.0 - t).powi(3);
+        outline.width = state.target_width * eased;
+        state.written_width = outline.width;
+
+        if t >= 1.0 {
+            outline.width = state.target_width;
+            commands.entity(entity).remove::<OutlineGrowInState>();
+        }
+    }
+
+    // Start from zero width on newly added outlines
+    for (entity, mut outline) in added.iter_mut() {
+        commands.entity(entity).insert(OutlineGrowInState {
+            target_width: outline.width,
+            written_width: Vec2::ZERO,
+            elapsed: 0.0,
+        });
+        outline.width = Vec2::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    fn grow_in_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                50,
+            )))
+            .add_systems(Update, update_outline_grow_in);
+        app
+    }
+
+    fn width(app: &App, entity: Entity) -> f32 {
+        app.world().get::<MeshOutline>(entity).unwrap().width.x
+    }
+
+    #[test]
+    fn new_outline_grows_to_its_width() {
+        let mut app = grow_in_app();
+        let entity = app
+            .world_mut()
+            .spawn((OutlineGrowIn::default(), MeshOutline::with_width(10.0)))
+            .id();
+
+        app.update();
+        assert_eq!(width(&app, entity), 0.0);
+
+        let mut last = 0.0;
+        for _ in 0..10 {
+            app.update();
+            let width = width(&app, entity);
+            assert!(width >= last);
+            last = width;
+        }
+        assert_eq!(last, 10.0);
+        assert!(app.world().get::<OutlineGrowInState>(entity).is_none());
+    }
+
+    #[test]
+    fn width_set_mid_animation_stops_it() {
+        let mut app = grow_in_app();
+        let entity = app
+            .world_mut()
+            .spawn((
+                OutlineGrowIn { duration: 10.0 },
+                MeshOutline::with_width(10.0),
+            ))
+            .id();
+        app.update();
+        app.update();
+
+        app.world_mut()
+            .get_mut::<MeshOutline>(entity)
+            .unwrap()
+            .width = Vec2::splat(4.0);
+        app.update();
+        app.update();
+
+        assert_eq!(width(&app, entity), 4.0);
+        assert!(app.world().get::<OutlineGrowInState>(entity).is_none());
+    }
+}
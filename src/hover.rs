@@ -0,0 +1,284 @@
+//! Hover outlines picked from the silhouette object IDs.
+//!
+//! Entities with [`OutlineHoverable`] get a silhouette, so their object ID is
+//! written into the silhouette texture, but no outline: their styles are
+//! marked pick-only, so they seed no Jump Flood. Each frame a compute pass
+//! reads the ID under the cursor and reads it back to the CPU, and only the
+//! hovered entity's outline is drawn. No picking backend is needed.
+
+use bevy::{
+    asset::RenderAssetUsages,
+    camera::RenderTarget,
+    prelude::*,
+    render::{
+        gpu_readback::{Readback, ReadbackComplete},
+        render_asset::RenderAssets,
+        render_resource::BufferUsages,
+        renderer::RenderQueue,
+        storage::{GpuShaderStorageBuffer, ShaderStorageBuffer},
+        sync_world::RenderEntity,
+        Extract,
+    },
+    window::{PrimaryWindow, WindowRef},
+};
+
+use crate::components::{MeshOutline, OutlineSettings};
+use crate::jfa_material::{HasSilhouetteMesh, OutlineCameraLink};
+
+/// Object ID read back when no silhouette is under the cursor
+const NO_OBJECT: u32 = u32::MAX;
+
+/// Marks an entity to be outlined while the cursor is over it.
+///
+/// The entity's [`MeshOutline`] is managed by [`OutlineHover`]; don't add
+/// one yourself. Only other hoverable or outlined entities occlude it, since
+/// picking reads the silhouette texture rather than the scene.
+#[derive(Component, Clone, Copy, Default)]
+pub struct OutlineHoverable;
+
+/// Hover outline configuration and the currently hovered entity.
+#[derive(Resource, Default)]
+pub struct OutlineHover {
+    /// Outline style of the hovered entity.
+    pub style: MeshOutline,
+    /// The [`OutlineHoverable`] entity under the cursor, if any.
+    pub hovered: Option<Entity>,
+}
+
+/// Per-camera GPU pick state of [`OutlineHover`]
+#[derive(Component)]
+pub struct OutlineHoverPick {
+    /// `[texel.x, texel.y, object_id, padding]` shared with the pick pass
+    pub buffer: Handle<ShaderStorageBuffer>,
+    /// Object ID under the cursor from the latest readback
+    pub object_id: u32,
+}
+
+/// Pick request extracted to the render world
+#[derive(Component)]
+pub struct ExtractedHoverPick {
+    pub buffer: Handle<ShaderStorageBuffer>,
+    /// Silhouette texel under the cursor, `u32::MAX` when off-screen
+    pub texel: [u32; 2],
+}
+
+/// System to give outlined cameras a pick buffer once hoverables exist
+pub fn setup_hover_picks(
+    mut commands: Commands,
+    mut buffers: ResMut<Assets<ShaderStorageBuffer>>,
+    hoverables: Query<(), With<OutlineHoverable>>,
+    cameras: Query<Entity, (With<OutlineCameraLink>, Without<OutlineHoverPick>)>,
+) {
+    if hoverables.is_empty() {
+        return;
+    }
+
+    for camera in cameras.iter() {
+        let initial = [u32::MAX, u32::MAX, NO_OBJECT, 0];
+        let mut buffer =
+            ShaderStorageBuffer::new(bytemuck::cast_slice(&initial), RenderAssetUsages::default());
+        // Read back to the CPU after the pick pass
+        buffer.buffer_description.usage |= BufferUsages::COPY_SRC | BufferUsages::COPY_DST;
+        let buffer = buffers.add(buffer);
+
+        // Child of the camera so it stops reading back when the camera is gone
+        commands
+            .spawn((Readback::buffer(buffer.clone()), ChildOf(camera)))
+            .observe(
+                move |event: On<ReadbackComplete>, mut picks: Query<&mut OutlineHoverPick>| {
+                    let Some(bytes) = event.data.get(8..12) else {
+                        return;
+                    };
+                    if let Ok(mut pick) = picks.get_mut(camera) {
+                        pick.object_id = u32::from_le_bytes(bytes.try_into().unwrap());
+                    }
+                },
+            );
+
+        commands.entity(camera).insert(OutlineHoverPick {
+            buffer,
+            object_id: NO_OBJECT,
+        });
+    }
+}
+
+/// System to resolve the picked object ID and show the hovered outline
+pub fn update_outline_hover(
+    mut commands: Commands,
+    mut hover: ResMut<OutlineHover>,
+    picks: Query<&OutlineHoverPick>,
+    sources: Query<(Entity, &HasSilhouetteMesh), With<OutlineHoverable>>,
+    hoverables: Query<(Entity, Has<MeshOutline>), With<OutlineHoverable>>,
+    // Style last given to every hoverable
+    mut applied_style: Local<Option<MeshOutline>>,
+) {
+    let picked = picks
+        .iter()
+        .map(|pick| pick.object_id)
+        .find(|&object_id| object_id != NO_OBJECT);
+    // Object IDs never exceed what the silhouette stores, so they match exactly
+    let hovered = picked.and_then(|picked| {
+        sources
+            .iter()
+            .find(|(_, has_silhouette)| has_silhouette.object_id == picked)
+            .map(|(entity, _)| entity)
+    });
+    if hover.hovered != hovered {
+        hover.hovered = hovered;
+    }
+
+    // Every hoverable carries the hover style so its ID stays pickable. Only
+    // the hovered one is drawn, the others are extracted as pick-only
+    let restyle = *applied_style != Some(hover.style);
+    *applied_style = Some(hover.style);
+    for (entity, has_outline) in hoverables.iter() {
+        if has_outline && !restyle {
+            continue;
+        }
+        commands.entity(entity).insert(hover.style);
+    }
+}
+
+/// Extracts the silhouette texel under the cursor for each picking camera
+pub fn extract_hover_picks(
+    mut commands: Commands,
+    cameras: Extract<
+        Query<(
+            &RenderEntity,
+            &Camera,
+            Option<&RenderTarget>,
+            &OutlineSettings,
+            &OutlineCameraLink,
+            &OutlineHoverPick,
+        )>,
+    >,
+    stale_cameras: Extract<Query<&RenderEntity, (With<OutlineSettings>, Without<OutlineHoverPick>)>>,
+    windows: Extract<Query<(&Window, Has<PrimaryWindow>)>>,
+    images: Extract<Res<Assets<Image>>>,
+) {
+    // Render world views persist, so stop picking on cameras that no longer do
+    for render_entity in stale_cameras.iter() {
+        commands
+            .entity(render_entity.id())
+            .remove::<ExtractedHoverPick>();
+    }
+
+    for (render_entity, camera, render_target, settings, link, pick) in cameras.iter() {
+        let window = match render_target {
+            Some(RenderTarget::Window(WindowRef::Entity(entity))) => {
+                windows.get(*entity).ok().map(|(window, _)| window)
+            }
+            Some(RenderTarget::Window(WindowRef::Primary)) | None => windows
+                .iter()
+                .find(|(_, primary)| *primary)
+                .map(|(window, _)| window),
+            _ => None,
+        };
+
+        // Cursor in silhouette texels, offset by the viewport and overscan.
+        // The texture may be sized apart from the view, e.g. by
+        // `OutlineTargetSize`, so the cursor is scaled to it first
+        let viewport_offset = camera
+            .viewport
+            .as_ref()
+            .map_or(Vec2::ZERO, |viewport| viewport.physical_position.as_vec2());
+        let margin = settings.overscan_margin() as f32;
+        let scale = images
+            .get(&link.silhouette_texture)
+            .zip(camera.physical_viewport_size())
+            .filter(|(_, view_size)| view_size.cmpgt(UVec2::ZERO).all())
+            .map_or(Vec2::ONE, |(image, view_size)| {
+                (image.size_f32() - 2.0 * margin) / view_size.as_vec2()
+            });
+        let texel = window
+            .and_then(Window::physical_cursor_position)
+            .map(|cursor| (cursor - viewport_offset) * scale + margin)
+            .filter(|texel| texel.cmpge(Vec2::ZERO).all())
+            .map_or([u32::MAX; 2], |texel| texel.as_uvec2().to_array());
+
+        commands
+            .entity(render_entity.id())
+            .insert(ExtractedHoverPick {
+                buffer: pick.buffer.clone(),
+                texel,
+            });
+    }
+}
+
+/// Uploads the cursor texel before the pick pass runs
+///
+/// The picked ID is reset as well, so frames where the outline node skips the
+/// pick pass, e.g. with no silhouette in view, read back no object instead of
+/// the last hovered one.
+pub fn prepare_hover_picks(
+    render_queue: Res<RenderQueue>,
+    buffers: Res<RenderAssets<GpuShaderStorageBuffer>>,
+    picks: Query<&ExtractedHoverPick>,
+) {
+    for pick in picks.iter() {
+        if let Some(buffer) = buffers.get(&pick.buffer) {
+            let [x, y] = pick.texel;
+            let data = [x, y, NO_OBJECT, 0];
+            render_queue.write_buffer(&buffer.buffer, 0, bytemuck::cast_slice(&data));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picked_hoverable_is_hovered_and_restyled() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<OutlineHover>()
+            .add_systems(Update, update_outline_hover);
+        let pick = app
+            .world_mut()
+            .spawn(OutlineHoverPick {
+                buffer: Handle::default(),
+                object_id: 7,
+            })
+            .id();
+        let mut spawn_hoverable = |object_id: u32| {
+            let silhouette = app.world_mut().spawn_empty().id();
+            app.world_mut()
+                .spawn((
+                    OutlineHoverable,
+                    HasSilhouetteMesh {
+                        silhouette,
+                        object_id,
+                    },
+                ))
+                .id()
+        };
+        let picked = spawn_hoverable(7);
+        let other = spawn_hoverable(8);
+
+        app.update();
+
+        let world = app.world();
+        assert_eq!(world.resource::<OutlineHover>().hovered, Some(picked));
+        // Every hoverable carries the style so it stays pickable
+        for entity in [picked, other] {
+            assert!(world.get::<MeshOutline>(entity).is_some());
+        }
+
+        // Changing the hover style restyles every hoverable
+        let color = LinearRgba::GREEN;
+        app.world_mut().resource_mut::<OutlineHover>().style.color = color;
+        app.update();
+        for entity in [picked, other] {
+            assert_eq!(app.world().get::<MeshOutline>(entity).unwrap().color, color);
+        }
+
+        // Nothing under the cursor
+        app.world_mut()
+            .get_mut::<OutlineHoverPick>(pick)
+            .unwrap()
+            .object_id = NO_OBJECT;
+        app.update();
+        assert_eq!(app.world().resource::<OutlineHover>().hovered, None);
+    }
+}
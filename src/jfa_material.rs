@@ -4,48 +4,254 @@
 //! 1. Init pass: Convert silhouette to seed coordinates
 //! 2. JFA passes: Propagate seeds with exponentially decreasing step sizes
 //! 3. Composite pass: Use distance field to render outline
+//!
+//! Cameras in [`OutlineMode::DepthEdges`] skip all of the above and run a single
+//! edge detection pass over their depth and normal prepasses instead.
+
+use std::any::TypeId;
 
 use bevy::{
     asset::RenderAssetUsages,
-    camera::{visibility::RenderLayers, RenderTarget},
-    core_pipeline::core_3d::graph::{Core3d, Node3d},
+    camera::{
+        primitives::Aabb,
+        visibility::{NoFrustumCulling, RenderLayers, VisibilityRange, VisibleEntities},
+        RenderTarget, SubCameraView,
+    },
+    core_pipeline::{
+        core_3d::graph::{Core3d, Node3d},
+        prepass::{DepthPrepass, NormalPrepass, ViewPrepassTextures},
+        tonemapping::{DebandDither, Tonemapping},
+    },
+    diagnostic::FrameCount,
+    ecs::entity::{EntityHashMap, EntityHashSet},
+    mesh::{skinning::SkinnedMesh, MeshTag},
+    platform::collections::{HashMap, HashSet},
     prelude::*,
     render::{
         render_asset::RenderAssets,
         render_graph::{
-            NodeRunError, RenderGraphContext, RenderGraphExt, RenderLabel, ViewNode, ViewNodeRunner,
+            NodeRunError, RenderGraph, RenderGraphContext, RenderGraphExt, RenderLabel, ViewNode,
+            ViewNodeRunner,
         },
         render_resource::{
-            binding_types::{sampler as sampler_layout, texture_2d, texture_storage_2d, uniform_buffer},
-            BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntries,
+            binding_types::{
+                sampler as sampler_layout, storage_buffer_read_only_sized, storage_buffer_sized,
+                texture_2d, texture_depth_2d, texture_storage_2d, uniform_buffer,
+            },
+            AddressMode, BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntries,
             Buffer, CachedComputePipelineId, CachedRenderPipelineId, ColorTargetState, ColorWrites,
             ComputePassDescriptor, ComputePipelineDescriptor, Extent3d, FragmentState,
             MultisampleState, Operations, PipelineCache, PrimitiveState, RenderPassColorAttachment,
-            RenderPassDescriptor, RenderPipelineDescriptor, Sampler, SamplerBindingType,
-            SamplerDescriptor, ShaderStages, ShaderType, StorageTextureAccess, TextureDimension,
-            TextureFormat, TextureSampleType, TextureUsages, TextureView, TextureViewDescriptor,
+            RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor, Sampler, SamplerBindingType,
+            SamplerDescriptor, ShaderStages, ShaderType, StorageTextureAccess, TextureDescriptor,
+            TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureView,
+            TextureViewDescriptor,
         },
         renderer::{RenderContext, RenderDevice, RenderQueue},
-        texture::GpuImage,
-        view::ViewTarget,
+        storage::GpuShaderStorageBuffer,
+        texture::{FallbackImage, GpuImage},
+        view::{Hdr, ViewTarget},
         Extract, Render, RenderApp,
     },
 };
 
-use crate::components::{MeshOutline, OutlineSettings};
+use crate::components::{
+    JfaSchedule, MeshOutline, OutlineFragmentShader, OutlineHatch, OutlineMode, OutlineSettings,
+    OutlineShape, OutlineStage, OutlineTargetSize, OutlineVertexShader, SilhouetteMeshOverride,
+    WidthUnit, MAX_OUTLINE_WIDTH,
+};
+use crate::hover::{
+    extract_hover_picks, prepare_hover_picks, ExtractedHoverPick, OutlineHover, OutlineHoverable,
+};
+use crate::selection::OutlineSelection;
 use crate::silhouette_material::SilhouetteMaterial;
 
-/// Render layer for silhouette rendering (layer 31 to avoid conflicts)
+/// Default render layer for silhouette rendering (layer 31 to avoid conflicts)
 pub const OUTLINE_RENDER_LAYER: usize = 31;
 
+/// Widest outline, in pixels, drawn by scanning the silhouette instead of the Jump Flood
+pub const THIN_OUTLINE_WIDTH: f32 = 4.0;
+
+/// Offset from [`OutlineRenderLayer`] of the per-camera silhouette layers
+/// used by [`OutlineAllow`] and [`OutlineDeny`]
+///
+/// The layers mapped from the app's layers end below this offset as long as
+/// [`OutlineRenderLayer`] is at most this value, which is asserted.
+pub const OUTLINE_CAMERA_LAYER_OFFSET: usize = 64;
+
+/// Render layer shared by silhouette meshes and silhouette cameras
+///
+/// This is the single source of truth for the silhouette layer. Changing it
+/// moves every silhouette mesh and silhouette camera to the new layer
+/// together. Pick a layer none of your own cameras render.
+///
+/// Layers from this one up are reserved for outlines; the app's own
+/// entities and cameras must stay on layers below it. Their
+/// [`RenderLayers`] map into the reserved range: layer `n` becomes
+/// `self.0 + n`, so a silhouette camera only sees silhouettes of entities
+/// sharing a layer with its main camera. Layers at or above `self.0` are
+/// left out of the mapping.
+///
+/// Each silhouette camera also has a private layer
+/// `self.0 + OUTLINE_CAMERA_LAYER_OFFSET + i`, holding the silhouettes of
+/// entities with [`OutlineAllow`] or [`OutlineDeny`] it outlines. The
+/// mapped layers end at `2 * self.0`, so `self.0` can be at most
+/// [`OUTLINE_CAMERA_LAYER_OFFSET`] for the two ranges not to overlap.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutlineRenderLayer(pub usize);
+
+impl Default for OutlineRenderLayer {
+    fn default() -> Self {
+        Self(OUTLINE_RENDER_LAYER)
+    }
+}
+
+impl OutlineRenderLayer {
+    /// The [`RenderLayers`] containing only this layer
+    pub fn render_layers(&self) -> RenderLayers {
+        RenderLayers::layer(self.0)
+    }
+
+    /// Silhouette layers matching an entity's or camera's `layers`, which
+    /// default to layer 0 when absent. Reserved layers in `layers` are
+    /// skipped, so they can't map onto other silhouette layers.
+    pub fn silhouette_layers(&self, layers: Option<&RenderLayers>) -> RenderLayers {
+        match layers {
+            Some(layers) => layers
+                .iter()
+                .filter(|&layer| layer < self.0)
+                .map(|layer| self.0 + layer)
+                .collect(),
+            None => self.render_layers(),
+        }
+    }
+
+    /// Private silhouette layer of the silhouette camera with `camera_index`
+    ///
+    /// # Panics
+    ///
+    /// If the layer is above [`OUTLINE_CAMERA_LAYER_OFFSET`], where the
+    /// layers mapped from the app's layers would overlap the camera layers.
+    pub fn camera_layer(&self, camera_index: usize) -> usize {
+        assert!(
+            self.0 <= OUTLINE_CAMERA_LAYER_OFFSET,
+            "OutlineRenderLayer({}) is above {OUTLINE_CAMERA_LAYER_OFFSET}, so the layers \
+             mapped from the app's overlap the per-camera silhouette layers",
+            self.0,
+        );
+        self.0 + OUTLINE_CAMERA_LAYER_OFFSET + camera_index
+    }
+
+    /// Layers of a silhouette camera whose main camera is on `layers`
+    pub fn silhouette_camera_layers(
+        &self,
+        layers: Option<&RenderLayers>,
+        camera_index: usize,
+    ) -> RenderLayers {
+        self.silhouette_layers(layers).with(self.camera_layer(camera_index))
+    }
+}
+
+/// Limits the cameras outlining this entity to the listed ones
+///
+/// Cameras are the main cameras carrying [`OutlineSettings`]. Layer
+/// filtering still applies: a listed camera must also share a
+/// [`RenderLayers`] layer with the entity.
+#[derive(Component, Clone, Debug, Default)]
+pub struct OutlineAllow(pub Vec<Entity>);
+
+/// Stops the listed cameras from outlining this entity
+///
+/// Denies specific objects on a layer the camera otherwise outlines, and
+/// combines with [`OutlineAllow`].
+#[derive(Component, Clone, Debug, Default)]
+pub struct OutlineDeny(pub Vec<Entity>);
+
+/// Render layer a camera must be on to draw outlines
+///
+/// With `Some(layer)`, only cameras whose [`RenderLayers`] include `layer`
+/// draw outlines; the others behave as if [`OutlineSettings::enabled`] were
+/// off. This lets every camera carry the same [`OutlineSettings`] while the
+/// layer picks which ones use them. `None`, the default, outlines on every
+/// camera with [`OutlineSettings`].
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OutlineCameraLayer(pub Option<usize>);
+
+impl OutlineCameraLayer {
+    /// Whether a camera on `layers`, layer 0 when absent, draws outlines
+    pub fn includes(&self, layers: Option<&RenderLayers>) -> bool {
+        match self.0 {
+            None => true,
+            Some(layer) => layers.map_or(layer == 0, |layers| {
+                layers.intersects(&RenderLayers::layer(layer))
+            }),
+        }
+    }
+}
+
 /// GPU uniform settings for the outline composite shader.
 #[derive(Clone, Copy, Default, PartialEq, ShaderType, bytemuck::Pod, bytemuck::Zeroable)]
 #[repr(C)]
 pub struct OutlineShaderSettings {
     pub color: [f32; 4],
-    pub width: f32,
+    /// Min and max UV of the region outlines are confined to
+    pub region: [f32; 4],
+    /// Horizontal and vertical outline width in pixels
+    pub width: Vec2,
     pub enabled: f32,
-    pub _padding: [f32; 2],
+    pub auto_contrast: f32,
+    pub near_fade_distance: f32,
+    /// Fraction of outline pixels kept by dithering, negative to disable
+    pub dither_opacity: f32,
+    /// Overscan margin of the outline textures in pixels
+    pub overscan: f32,
+    /// Elapsed seconds, wrapped, scrolling each object's dashes by its
+    /// [`OutlineObjectStyle::dash_speed`]
+    pub time: f32,
+    pub temporal_smoothing: f32,
+    /// View height in pixels and DPI scale factor, to convert each object's
+    /// [`OutlineObjectStyle::width`] to pixels
+    pub view_height: f32,
+    pub scale_factor: f32,
+    /// Whether any object has an interior fill
+    pub fill: f32,
+    /// Keep the scene alpha under outlines
+    pub preserve_alpha: f32,
+    /// Blend alpha with the premultiplied "over" operator, see
+    /// [`OutlineSettings::premultiplied_alpha`]
+    pub premultiplied_alpha: f32,
+    /// Pixels per world unit, at unit depth for perspective projections,
+    /// or 0 if unknown
+    pub size_scale: f32,
+    /// Near plane of a perspective projection, 0 for orthographic
+    pub perspective_near: f32,
+    /// Far minus near plane of an orthographic projection, to compare the
+    /// depth prepass with silhouette distances for
+    /// [`MeshOutline::backlight`]
+    pub orthographic_depth: f32,
+    /// Find seeds by scanning the silhouette instead of the Jump Flood, see
+    /// [`THIN_OUTLINE_WIDTH`]
+    pub dilate: f32,
+    /// Draw onto a transparent texture instead of over the scene, see
+    /// [`OutlineSettings::auto_composite`]
+    pub detached: f32,
+    pub _padding: f32,
+}
+
+/// GPU uniform settings for the depth edge shader.
+#[derive(Clone, Copy, Default, PartialEq, ShaderType, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct DepthEdgeShaderSettings {
+    pub color: [f32; 4],
+    pub width: f32,
+    pub depth_threshold: f32,
+    pub normal_threshold: f32,
+    /// Near plane of a perspective projection, 0 for orthographic
+    pub perspective_near: f32,
+    /// Blend alpha with the premultiplied "over" operator, see
+    /// [`OutlineSettings::premultiplied_alpha`]
+    pub premultiplied_alpha: f32,
+    pub _padding: [f32; 3],
 }
 
 /// GPU uniform for JFA step pass
@@ -57,12 +263,94 @@ pub struct JfaStepParams {
 }
 
 /// Links the main camera to its silhouette camera and textures
+///
+/// Inserted on cameras with [`OutlineSettings`] once their silhouette camera
+/// is set up. The textures can be bound in custom materials; all of them
+/// span the view plus [`OutlineSettings::overscan_margin`] pixels on each side.
 #[derive(Component, Clone)]
 pub struct OutlineCameraLink {
+    /// Camera rendering the silhouette copies
     pub silhouette_camera: Entity,
+    /// `Rgba16Float` silhouette data: red is the distance from the near
+    /// plane, green/blue the object ID low/high byte and alpha the coverage.
+    /// Covered texels store `1 +` the vertex color packed as RGB 4-4-3 bits
+    /// of its square root in alpha, so any alpha above `0.5` is covered
     pub silhouette_texture: Handle<Image>,
+    /// `Rg16Unorm` Jump Flood buffer, see [`distance_field`](Self::distance_field)
     pub jfa_ping_texture: Handle<Image>,
+    /// `Rg16Unorm` Jump Flood buffer, see [`distance_field`](Self::distance_field)
     pub jfa_pong_texture: Handle<Image>,
+    /// `Rgba16Float` outline strength of the current and previous frame, used
+    /// for [`OutlineSettings::temporal_smoothing`]. Red is the strength,
+    /// green/blue the outlined object's ID bytes and alpha whether there is
+    /// one, so outlines kept alive by history keep their color. Only
+    /// allocated while smoothing is on. Unlike the other textures these match
+    /// the view size, without overscan.
+    pub history_textures: Option<[Handle<Image>; 2]>,
+    /// `Rgba16Float` outlines and fills drawn over transparent black, with
+    /// premultiplied alpha, while [`OutlineSettings::auto_composite`] is
+    /// disabled. Blend it over the scene with `color + scene * (1 - alpha)`.
+    /// Matches the view size, without overscan.
+    pub outline_texture: Handle<Image>,
+    /// Index of the silhouette camera's private layer, see
+    /// [`OutlineRenderLayer::camera_layer`]
+    pub camera_index: usize,
+}
+
+impl OutlineCameraLink {
+    /// The Jump Flood texture holding the final seed field for `settings`,
+    /// or `None` unless [`OutlineSettings::distance_field`] is enabled.
+    ///
+    /// Each texel stores the texel coordinates of the nearest silhouette
+    /// pixel plus one, divided by 65535, or `(0, 0)` if none is within
+    /// [`OutlineSettings::max_width`]. `round(seed * 65535.0) - 1.0` is the
+    /// seed's texel, so the distance to the silhouette in pixels is
+    /// `length(texel + 1.0 - round(seed * 65535.0))`.
+    ///
+    /// Seeds are exact integers in `Rg16Unorm`, so the field doesn't quantize
+    /// on large targets the way `f16` or unorm UVs would: 1/65535 of a UV is
+    /// already 0.12 pixels at 8K, more with an overscan margin.
+    pub fn distance_field(&self, settings: &OutlineSettings) -> Option<&Handle<Image>> {
+        if !settings.distance_field {
+            return None;
+        }
+        // Step passes alternate ping -> pong -> ping, starting from ping
+        if settings.jfa_pass_count().is_multiple_of(2) {
+            Some(&self.jfa_ping_texture)
+        } else {
+            Some(&self.jfa_pong_texture)
+        }
+    }
+}
+
+/// Render world component giving other effects the view's Jump Flood field
+///
+/// Inserted on views with [`OutlineSettings::distance_field`] enabled whose
+/// outline node computes the field this frame. Render graph nodes ordered
+/// after the view's [`OutlineStageNodeLabel`] can bind
+/// [`texture`](Self::texture) instead of running a Jump Flood of their own.
+/// The contents are described by [`OutlineCameraLink::distance_field`].
+#[derive(Component, Clone)]
+pub struct OutlineDistanceField {
+    pub texture: Handle<Image>,
+    /// [`FrameCount`] the field was computed in. The component stays on the
+    /// view once outlines stop, so compare this to the current frame.
+    pub frame: u32,
+}
+
+/// Render world component giving custom compositing the view's outlines
+///
+/// Inserted on views whose [`OutlineSettings::auto_composite`] is disabled.
+/// Render graph nodes ordered after the view's [`OutlineStageNodeLabel`] can
+/// blend [`texture`](Self::texture) themselves, see
+/// [`OutlineCameraLink::outline_texture`].
+#[derive(Component, Clone)]
+pub struct OutlineTexture {
+    pub texture: Handle<Image>,
+    /// [`FrameCount`] the outlines were drawn in. The component stays on the
+    /// view once auto compositing is re-enabled, so compare this to the
+    /// current frame.
+    pub frame: u32,
 }
 
 /// Extracted outline data for render world
@@ -71,9 +359,124 @@ pub struct ExtractedOutlineData {
     pub silhouette_texture: Handle<Image>,
     pub jfa_ping_texture: Handle<Image>,
     pub jfa_pong_texture: Handle<Image>,
+    /// History textures read and written this frame, the first holding last
+    /// frame's outline strength, while temporal smoothing is on
+    pub history_textures: Option<[Handle<Image>; 2]>,
+    /// Texture the composite pass draws into instead of the view target,
+    /// see [`OutlineSettings::auto_composite`]
+    pub outline_texture: Option<Handle<Image>>,
+    /// Pixels the Jump Flood reaches this frame: the widest outline drawn,
+    /// capped at [`OutlineSettings::max_width`]
+    pub jfa_width: u32,
+    pub jfa_schedule: JfaSchedule,
+    /// Whether any object draws an outline, rather than only being in the
+    /// silhouette for hover picking. Without one only the pick pass runs
+    pub draws_outlines: bool,
     pub settings: OutlineShaderSettings,
 }
 
+/// GPU style of a single outlined object
+#[derive(Clone, Copy, Default, PartialEq, ShaderType, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct OutlineObjectStyle {
+    pub color: [f32; 4],
+    /// Interior tint, transparent for no fill
+    pub fill: [f32; 4],
+    /// Take the outline color from the silhouette's vertex colors
+    pub vertex_colors: f32,
+    /// Hatch line spacing in pixels, zero for a solid fill
+    pub hatch_spacing: f32,
+    /// Hatch line direction in radians
+    pub hatch_angle: f32,
+    /// Hatch line thickness in pixels
+    pub hatch_width: f32,
+    /// World-space bounding sphere radius, 0 if unknown
+    pub radius: f32,
+    /// Fade the outline out from the silhouette edge behind nearer geometry
+    pub backlight: f32,
+    /// Horizontal and vertical outline width in [`width_unit`](Self::width_unit)
+    pub width: [f32; 2],
+    /// [`WidthUnit`] of the width: 0 for pixels, 1 for a viewport fraction,
+    /// 2 for logical pixels
+    pub width_unit: f32,
+    /// Only drawn into the silhouette for hover picking: the object seeds no
+    /// outline and gets no fill
+    pub pick_only: f32,
+    /// Dash and gap lengths in pixels along the contour, 0 gap for a solid
+    /// outline
+    pub dash_length: f32,
+    pub dash_gap: f32,
+    /// Scroll speed of the dashes in pixels per second
+    pub dash_speed: f32,
+    pub _padding: [f32; 3],
+}
+
+impl OutlineObjectStyle {
+    fn new(outline: &MeshOutline) -> Self {
+        let hatch = outline.hatch.unwrap_or(OutlineHatch {
+            spacing: 0.0,
+            angle: 0.0,
+            line_width: 0.0,
+        });
+        let dash = outline.dash.filter(|dash| dash.gap > 0.0);
+        // Hatching without a fill draws its lines in the outline color
+        let fill = match (outline.fill, outline.hatch) {
+            (Some(fill), _) => fill,
+            (None, Some(_)) => outline.color,
+            (None, None) => LinearRgba::NONE,
+        };
+        Self {
+            color: outline.color.to_f32_array(),
+            fill: fill.to_f32_array(),
+            vertex_colors: if outline.vertex_colors { 1.0 } else { 0.0 },
+            hatch_spacing: hatch.spacing.max(0.0),
+            hatch_angle: hatch.angle,
+            hatch_width: hatch.line_width.max(0.0),
+            radius: 0.0,
+            backlight: if outline.backlight { 1.0 } else { 0.0 },
+            width: outline.width.to_array(),
+            width_unit: match outline.width_unit {
+                WidthUnit::Pixels => 0.0,
+                WidthUnit::ViewportFraction => 1.0,
+                WidthUnit::LogicalPixels => 2.0,
+            },
+            pick_only: 0.0,
+            dash_length: dash.map_or(0.0, |dash| dash.length.max(0.0)),
+            dash_gap: dash.map_or(0.0, |dash| dash.gap),
+            dash_speed: dash.map_or(0.0, |dash| dash.speed),
+            _padding: [0.0; 3],
+        }
+    }
+}
+
+/// Style of each outlined object, indexed by the object ID written into the
+/// silhouette texture
+#[derive(Resource, Default)]
+pub struct ExtractedOutlineObjectStyles(pub Vec<OutlineObjectStyle>);
+
+/// GPU storage buffer holding [`ExtractedOutlineObjectStyles`]
+#[derive(Resource)]
+pub struct OutlineObjectStyleBuffer {
+    pub buffer: Buffer,
+    /// Number of styles the buffer can hold
+    pub capacity: usize,
+    /// Styles last uploaded, to only upload the ones that changed
+    pub uploaded: Vec<OutlineObjectStyle>,
+}
+
+/// Extracted depth edge data for render world
+#[derive(Component, Clone)]
+pub struct ExtractedDepthEdgeData {
+    pub settings: DepthEdgeShaderSettings,
+}
+
+/// Cached GPU resources for depth edge rendering (per-camera)
+#[derive(Component)]
+pub struct DepthEdgeRenderResources {
+    pub settings_buffer: Buffer,
+    pub cached_settings: DepthEdgeShaderSettings,
+}
+
 /// Cached GPU resources for outline rendering (per-camera)
 /// These are created once and reused each frame to avoid allocation overhead
 #[derive(Component)]
@@ -81,12 +484,11 @@ pub struct ExtractedOutlineData {
 pub struct OutlineRenderResources {
     pub ping_view: TextureView,
     pub pong_view: TextureView,
-    pub init_bind_group: BindGroup,
     pub step_bind_groups: Vec<BindGroup>,
     pub step_buffers: Vec<Buffer>,
     pub settings_buffer: Buffer,
     /// Cached values to detect when resources need recreation
-    pub cached_width: f32,
+    pub cached_step_sizes: Vec<u32>,
     pub cached_texture_size: (u32, u32),
     /// Cached settings to avoid unnecessary buffer writes
     pub cached_settings: OutlineShaderSettings,
@@ -96,60 +498,240 @@ pub struct OutlineRenderResources {
 #[derive(Component)]
 pub struct SilhouetteCamera;
 
+/// Prepasses and MSAA a camera had before [`setup_depth_edge_cameras`]
+/// changed them for [`OutlineMode::DepthEdges`]
+#[derive(Component)]
+pub struct DepthEdgeCameraState {
+    depth_prepass: bool,
+    normal_prepass: bool,
+    msaa: Option<Msaa>,
+}
+
 /// Marker for silhouette mesh copies
 #[derive(Component)]
-pub struct SilhouetteMesh;
+pub struct SilhouetteMesh {
+    /// The outlined entity this silhouette copies
+    pub source: Entity,
+}
 
 /// Marker component added to source entities that have a silhouette mesh spawned
 #[derive(Component)]
 pub struct HasSilhouetteMesh {
     pub silhouette: Entity,
+    pub object_id: u32,
+}
+
+/// Largest object ID the silhouette texture can store, in two 8 bit channels
+pub const MAX_OBJECT_ID: u32 = 0xFFFF;
+
+/// Allocates the per-object IDs written into the silhouette texture.
+/// IDs are passed to the silhouette shader through [`MeshTag`] and reused once freed.
+#[derive(Resource, Default)]
+pub struct OutlineObjectIds {
+    next: u32,
+    free: Vec<u32>,
+}
+
+impl OutlineObjectIds {
+    /// Take an unused object ID, or `None` if all IDs up to
+    /// [`MAX_OBJECT_ID`] are in use. Larger IDs would alias smaller ones in
+    /// the silhouette texture.
+    pub fn allocate(&mut self) -> Option<u32> {
+        if let Some(id) = self.free.pop() {
+            return Some(id);
+        }
+        if self.next > MAX_OBJECT_ID {
+            warn_once!(
+                "More than {} outlined objects, further outlines are skipped",
+                MAX_OBJECT_ID + 1
+            );
+            return None;
+        }
+        let id = self.next;
+        self.next += 1;
+        Some(id)
+    }
+
+    /// Return an object ID so it can be reused.
+    pub fn free(&mut self, id: u32) {
+        self.free.push(id);
+    }
 }
 
-/// Render label for the outline node
+/// Render label for the outline node of each stage
 #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
-pub struct OutlineNodeLabel;
+pub struct OutlineStageNodeLabel(pub OutlineStage);
+
+/// Render label for the outline node of the default
+/// [`OutlineStage::AfterTonemapping`], between tonemapping and the end of
+/// main pass post-processing
+///
+/// This used to be a unit struct for the single outline node, so existing
+/// graph edges naming it keep working.
+#[allow(non_upper_case_globals)]
+pub const OutlineNodeLabel: OutlineStageNodeLabel =
+    OutlineStageNodeLabel(OutlineStage::AfterTonemapping);
 
 /// Resource holding the silhouette material
 #[derive(Resource, Clone)]
 pub struct SilhouetteWhiteMaterial(pub Handle<SilhouetteMaterial>);
 
+/// Size the outline textures of a camera should have.
+///
+/// Uses [`OutlineTargetSize`] when present, otherwise infers it from the render target.
+/// Targets that can't be looked up directly, such as texture views or a
+/// missing window in headless apps, fall back to the size the camera computed.
+fn outline_texture_size(
+    camera: &Camera,
+    render_target: Option<&RenderTarget>,
+    target_size: Option<&OutlineTargetSize>,
+    windows: &Query<&Window>,
+    images: &Assets<Image>,
+) -> Option<UVec2> {
+    if let Some(OutlineTargetSize(size)) = target_size {
+        return Some(*size);
+    }
+
+    let size = match render_target {
+        Some(RenderTarget::Window(window_ref)) => {
+            let window = match window_ref {
+                bevy::window::WindowRef::Primary => windows.iter().next(),
+                bevy::window::WindowRef::Entity(e) => windows.get(*e).ok(),
+            };
+            window.map(|w| UVec2::new(w.physical_width(), w.physical_height()))
+        }
+        Some(RenderTarget::Image(image_target)) => {
+            images.get(&image_target.handle).map(|img| img.size())
+        }
+        Some(RenderTarget::None { size }) => Some(*size),
+        Some(RenderTarget::TextureView(_)) => None,
+        None => {
+            // Default to primary window
+            windows
+                .iter()
+                .next()
+                .map(|w| UVec2::new(w.physical_width(), w.physical_height()))
+        }
+    };
+    size.or_else(|| camera.physical_target_size())
+}
+
+/// Sub view rendering `margin` extra pixels around a `view_size` view, or
+/// `None` to render the view as is.
+fn overscan_sub_view(view_size: UVec2, margin: u32) -> Option<SubCameraView> {
+    (margin > 0).then(|| SubCameraView {
+        full_size: view_size,
+        offset: Vec2::splat(-(margin as f32)),
+        size: view_size + UVec2::splat(2 * margin),
+    })
+}
+
+/// Clamps oversized [`OutlineSettings::max_width`] values, warning once per change
+pub fn limit_outline_max_width(
+    mut cameras: Query<
+        (
+            Entity,
+            &Camera,
+            &mut OutlineSettings,
+            Option<&RenderTarget>,
+            Option<&OutlineTargetSize>,
+        ),
+        Changed<OutlineSettings>,
+    >,
+    windows: Query<&Window>,
+    images: Res<Assets<Image>>,
+) {
+    for (entity, camera, mut settings, render_target, target_size) in cameras.iter_mut() {
+        let target_limit =
+            outline_texture_size(camera, render_target, target_size, &windows, &images)
+                .map_or(u32::MAX, |size| size.min_element().max(1));
+        let limit = MAX_OUTLINE_WIDTH.min(target_limit);
+
+        if settings.max_width > limit {
+            warn!(
+                "OutlineSettings::max_width of {} on camera {entity} is too large, clamping to {limit}",
+                settings.max_width
+            );
+            settings.max_width = limit;
+        }
+    }
+}
+
 /// System to set up silhouette camera for main cameras with OutlineSettings
+///
+/// The silhouette camera is always [`Hdr`] with tonemapping, dithering and
+/// MSAA off, whatever the main camera uses. Silhouette pipelines are
+/// specialized for the silhouette camera's own view, so HDR and SDR main
+/// cameras get identical silhouettes; only the composite pass picks an HDR
+/// or SDR pipeline to match the main camera's target.
+///
+/// The silhouette shader writes its values directly without applying the
+/// view's exposure, and the main camera's `Tonemapping`, `Exposure` or
+/// color grading are never copied over, so coverage stays a clean 0 or 1
+/// however the scene is exposed.
+///
+/// Every camera with [`OutlineSettings`] gets its own silhouette camera and
+/// textures, so a portal camera rendering to an image outlines what it sees
+/// independently of the main view. Silhouette cameras render before every
+/// other camera, one [`Camera::order`] below the lowest, so they never tie
+/// with a camera of the app, see [`silhouette_camera_order`].
+///
+/// Cameras with [`OutlineSettings::enabled`] off, or outside the
+/// [`OutlineCameraLayer`], stay dormant: nothing is allocated for them until
+/// they are first enabled.
 pub fn setup_outline_camera(
     mut commands: Commands,
     mut images: ResMut<Assets<Image>>,
     mut materials: ResMut<Assets<SilhouetteMaterial>>,
+    white_material: Option<Res<SilhouetteWhiteMaterial>>,
+    outline_layer: Res<OutlineRenderLayer>,
+    camera_layer: Res<OutlineCameraLayer>,
     cameras: Query<
-        (Entity, &Camera, &Transform, &Projection, Option<&RenderTarget>),
-        (With<OutlineSettings>, Without<OutlineCameraLink>),
+        (
+            Entity,
+            &Camera,
+            &GlobalTransform,
+            &Projection,
+            Option<&RenderTarget>,
+            Option<&OutlineTargetSize>,
+            &OutlineSettings,
+            Option<&RenderLayers>,
+        ),
+        Without<OutlineCameraLink>,
     >,
+    links: Query<&OutlineCameraLink>,
+    other_cameras: Query<&Camera, Without<SilhouetteCamera>>,
     windows: Query<&Window>,
 ) {
-    for (entity, _camera, transform, projection, render_target) in cameras.iter() {
-        // Get the camera's target size
-        let size = match render_target {
-            Some(RenderTarget::Window(window_ref)) => {
-                let window = match window_ref {
-                    bevy::window::WindowRef::Primary => windows.iter().next(),
-                    bevy::window::WindowRef::Entity(e) => windows.get(*e).ok(),
-                };
-                window.map(|w| UVec2::new(w.physical_width(), w.physical_height()))
-            }
-            Some(RenderTarget::Image(image_target)) => {
-                images.get(&image_target.handle).map(|img| img.size())
-            }
-            _ => {
-                // Default to primary window
-                windows
-                    .iter()
-                    .next()
-                    .map(|w| UVec2::new(w.physical_width(), w.physical_height()))
-            }
-        };
+    let order = silhouette_camera_order(other_cameras.iter());
+    let mut has_white_material = white_material.is_some();
+    let mut camera_indices: Vec<usize> = links.iter().map(|link| link.camera_index).collect();
+    for (
+        entity,
+        camera,
+        global_transform,
+        projection,
+        render_target,
+        target_size,
+        settings,
+        render_layers,
+    ) in cameras.iter()
+    {
+        // Depth edge cameras work from their own prepasses
+        if !settings.renders_silhouettes() || !camera_layer.includes(render_layers) {
+            continue;
+        }
 
-        let size = size.unwrap_or(UVec2::new(1920, 1080));
+        // Get the camera's target size
+        // Sizes not known yet are corrected by resize_silhouette_textures
+        let view_size =
+            outline_texture_size(camera, render_target, target_size, &windows, &images)
+                .unwrap_or(UVec2::new(1920, 1080));
+        let margin = settings.overscan_margin();
+        let size = view_size + UVec2::splat(2 * margin);
 
         // Create silhouette render texture
+        // Float format so the red channel can carry the distance from the near plane
         let mut silhouette_image = Image::new_fill(
             Extent3d {
                 width: size.x.max(1),
@@ -157,8 +739,8 @@ pub fn setup_outline_camera(
                 depth_or_array_layers: 1,
             },
             TextureDimension::D2,
-            &[0, 0, 0, 0],
-            TextureFormat::Rgba8UnormSrgb,
+            &[0; 8], // 4 x f16 = 8 bytes
+            TextureFormat::Rgba16Float,
             RenderAssetUsages::RENDER_WORLD,
         );
         silhouette_image.texture_descriptor.usage =
@@ -173,7 +755,8 @@ pub fn setup_outline_camera(
         };
 
         // JFA textures need STORAGE_BINDING for compute shaders
-        // Using Rg16Unorm instead of Rg16Float - sufficient for UV coords in [0,1] range
+        // Using Rg16Unorm instead of Rg16Float - seeds store their texel + 1 in its
+        // 1/65535 steps, exact at any resolution where f16 or UVs would band
         let mut jfa_ping_image = Image::new_fill(
             jfa_extent,
             TextureDimension::D2,
@@ -196,25 +779,57 @@ pub fn setup_outline_camera(
             TextureUsages::TEXTURE_BINDING | TextureUsages::STORAGE_BINDING;
         let jfa_pong_handle = images.add(jfa_pong_image);
 
-        // Create silhouette material (minimal shader, no PBR)
-        let white_material = materials.add(SilhouetteMaterial::default());
-
-        // Store the white material handle for silhouette meshes
-        commands.insert_resource(SilhouetteWhiteMaterial(white_material));
+        // Outlines for custom compositing, drawn over transparent black
+        let mut outline_image = Image::new_fill(
+            Extent3d {
+                width: view_size.x.max(1),
+                height: view_size.y.max(1),
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0; 8], // 4 x f16 = 8 bytes
+            TextureFormat::Rgba16Float,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        outline_image.texture_descriptor.usage =
+            TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING;
+        let outline_handle = images.add(outline_image);
+
+        // Lowest private layer index not taken by another camera
+        let camera_index = (0..)
+            .find(|index| !camera_indices.contains(index))
+            .unwrap_or_default();
+        camera_indices.push(camera_index);
+
+        // Create silhouette material (minimal shader, no PBR), shared by the
+        // silhouettes of all cameras so they keep batching together
+        if !has_white_material {
+            let white_material = materials.add(SilhouetteMaterial::default());
+            commands.insert_resource(SilhouetteWhiteMaterial(white_material));
+            has_white_material = true;
+        }
 
         // Spawn silhouette camera
         let silhouette_camera = commands
             .spawn((
                 Camera3d::default(),
                 Camera {
-                    order: -1, // Render before main camera
+                    order, // Render before every other camera
                     clear_color: ClearColorConfig::Custom(Color::NONE),
+                    sub_camera_view: overscan_sub_view(view_size, margin),
                     ..default()
                 },
                 RenderTarget::Image(silhouette_handle.clone().into()),
-                *transform,
+                // Silhouette data must reach the texture unmodified:
+                // float intermediate, no tonemapping, dithering or MSAA resolve
+                Hdr,
+                Tonemapping::None,
+                DebandDither::Disabled,
+                Msaa::Off,
+                global_transform.compute_transform(),
+                *global_transform,
                 projection.clone(),
-                RenderLayers::layer(OUTLINE_RENDER_LAYER),
+                outline_layer.silhouette_camera_layers(render_layers, camera_index),
                 SilhouetteCamera,
             ))
             .id();
@@ -225,129 +840,820 @@ pub fn setup_outline_camera(
             silhouette_texture: silhouette_handle,
             jfa_ping_texture: jfa_ping_handle,
             jfa_pong_texture: jfa_pong_handle,
+            // Allocated by resize_silhouette_textures once smoothing is on
+            history_textures: None,
+            outline_texture: outline_handle,
+            camera_index,
         });
     }
 }
 
+/// Inserts the prepasses required by [`OutlineMode::DepthEdges`] cameras
+///
+/// Only missing prepasses are added, and MSAA is only turned off if it was
+/// on, as edges are read from single-sampled prepass textures. What the
+/// camera had before is kept in [`DepthEdgeCameraState`] and restored once
+/// it leaves the mode.
+pub fn setup_depth_edge_cameras(
+    mut commands: Commands,
+    cameras: Query<
+        (
+            Entity,
+            &OutlineSettings,
+            Has<DepthPrepass>,
+            Has<NormalPrepass>,
+            Option<&Msaa>,
+            Option<&DepthEdgeCameraState>,
+        ),
+        Changed<OutlineSettings>,
+    >,
+) {
+    for (entity, settings, depth_prepass, normal_prepass, msaa, state) in cameras.iter() {
+        let mut camera = commands.entity(entity);
+        match (settings.mode, state) {
+            (OutlineMode::DepthEdges(_), None) => {
+                if !depth_prepass {
+                    camera.insert(DepthPrepass);
+                }
+                if !normal_prepass {
+                    camera.insert(NormalPrepass);
+                }
+                if msaa.is_some_and(|msaa| *msaa != Msaa::Off) {
+                    camera.insert(Msaa::Off);
+                }
+                camera.insert(DepthEdgeCameraState {
+                    depth_prepass,
+                    normal_prepass,
+                    msaa: msaa.copied(),
+                });
+            }
+            (OutlineMode::Silhouette, Some(state)) => {
+                if !state.depth_prepass {
+                    camera.remove::<DepthPrepass>();
+                }
+                if !state.normal_prepass {
+                    camera.remove::<NormalPrepass>();
+                }
+                if let Some(msaa) = state.msaa {
+                    camera.insert(msaa);
+                }
+                camera.remove::<DepthEdgeCameraState>();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Spawns the silhouette copy of `source`, shared by [`sync_outline_meshes`]
+/// and [`sync_selection_meshes`](crate::selection::sync_selection_meshes)
+///
+/// `mesh` is the mesh to draw, i.e. the [`SilhouetteMeshOverride`] if any.
+pub(crate) fn spawn_silhouette<'a>(
+    commands: &'a mut Commands,
+    source: Entity,
+    object_id: u32,
+    material: &SilhouetteWhiteMaterial,
+    global_transform: &GlobalTransform,
+    layers: RenderLayers,
+    mesh: &Handle<Mesh>,
+    mirrored: (Option<&VisibilityRange>, Option<&SkinnedMesh>, bool),
+) -> EntityCommands<'a> {
+    let mut silhouette = commands.spawn((
+        SilhouetteMesh { source },
+        Mesh3d(mesh.clone()),
+        MeshMaterial3d(material.0.clone()),
+        MeshTag(object_id),
+        global_transform.compute_transform(),
+        *global_transform,
+        layers,
+    ));
+    mirror_silhouette_source(&mut silhouette, mirrored);
+    silhouette
+}
+
+/// Copies the source's LOD range, skin and culling opt-out to its silhouette
+///
+/// Sharing the [`VisibilityRange`] makes both show the same level, and
+/// skinning with the source's joints makes animation deform both alike.
+pub(crate) fn mirror_silhouette_source(
+    silhouette: &mut EntityCommands,
+    (range, skin, no_culling): (Option<&VisibilityRange>, Option<&SkinnedMesh>, bool),
+) {
+    match range {
+        Some(range) => silhouette.insert(range.clone()),
+        None => silhouette.remove::<VisibilityRange>(),
+    };
+    match skin {
+        Some(skin) => silhouette.insert(skin.clone()),
+        None => silhouette.remove::<SkinnedMesh>(),
+    };
+    match no_culling {
+        true => silhouette.insert(NoFrustumCulling),
+        false => silhouette.remove::<NoFrustumCulling>(),
+    };
+}
+
 /// System to sync silhouette meshes with outlined entities
+///
+/// Silhouettes share the source's mesh handle, so a mesh that is still
+/// loading needs no special handling: the silhouette draws nothing until the
+/// asset is ready, then gets its bounds and renders like the source does.
+///
+/// Silhouettes of a mesh share its handle and the white material, or the
+/// matching material of their shared source material from
+/// [`sync_silhouette_masks`], with the object ID in a per-instance
+/// [`MeshTag`]. Bevy batches all of them into a single instanced draw, so
+/// thousands of instances of a few meshes cost a few draws without a
+/// separate instancing path.
+///
+/// Skinned sources share their [`SkinnedMesh`] joints with the silhouette,
+/// so GPU skinning deforms both identically even when the source's own
+/// transform never changes.
+///
+/// Silhouettes copy [`NoFrustumCulling`] from their source, so sources that
+/// opt out of culling, such as huge terrain, aren't culled in the silhouette
+/// pass either.
 pub fn sync_outline_meshes(
     mut commands: Commands,
     white_material: Option<Res<SilhouetteWhiteMaterial>>,
+    mut object_ids: ResMut<OutlineObjectIds>,
+    outline_layer: Res<OutlineRenderLayer>,
     // Only query entities that don't already have a silhouette spawned
     outlined: Query<
-        (Entity, &Mesh3d, &GlobalTransform),
+        (
+            Entity,
+            &Mesh3d,
+            &GlobalTransform,
+            Option<&VisibilityRange>,
+            Option<&SkinnedMesh>,
+            Option<&RenderLayers>,
+            Has<NoFrustumCulling>,
+            Option<&SilhouetteMeshOverride>,
+        ),
         (With<MeshOutline>, Without<HasSilhouetteMesh>),
     >,
-    mut silhouettes: Query<(Entity, &mut Transform), (With<SilhouetteMesh>, Without<MeshOutline>)>,
+    mut silhouettes: Query<
+        (&mut Transform, &mut GlobalTransform),
+        (With<SilhouetteMesh>, Without<MeshOutline>),
+    >,
     // Only query sources with changed transforms
     changed_sources: Query<(Entity, &GlobalTransform), (With<MeshOutline>, Changed<GlobalTransform>)>,
+    // Sources that changed visibility ranges, skins or culling. Mesh swaps
+    // are followed by `sync_silhouette_shapes`
+    changed_mirrored: Query<
+        Entity,
+        (
+            With<MeshOutline>,
+            Or<(
+                Changed<VisibilityRange>,
+                Changed<SkinnedMesh>,
+                Added<NoFrustumCulling>,
+            )>,
+        ),
+    >,
+    mut removed_ranges: RemovedComponents<VisibilityRange>,
+    mut removed_skins: RemovedComponents<SkinnedMesh>,
+    mut removed_no_culling: RemovedComponents<NoFrustumCulling>,
+    // Sources that lost their mesh, e.g. while it is being reloaded
+    mut removed_meshes: RemovedComponents<Mesh3d>,
     // Track entities that had MeshOutline removed
     mut removed: RemovedComponents<MeshOutline>,
-    // Query to get the silhouette entity from source
-    sources_with_silhouettes: Query<(Entity, &HasSilhouetteMesh)>,
+    // Query to get the silhouette entity and mirrored components from source
+    sources_with_silhouettes: Query<(
+        Entity,
+        &HasSilhouetteMesh,
+        Option<&VisibilityRange>,
+        Option<&SkinnedMesh>,
+        Has<NoFrustumCulling>,
+    )>,
+    all_silhouettes: Query<(Entity, &SilhouetteMesh, &MeshTag)>,
+    outline_cameras: Query<&OutlineSettings>,
 ) {
     let Some(white_material) = white_material else {
         return;
     };
 
-    // Add silhouette meshes for new outlined entities
-    for (entity, mesh, global_transform) in outlined.iter() {
-        let (scale, rotation, translation) = global_transform.to_scale_rotation_translation();
+    // Silhouettes are only needed while some camera renders them
+    let needs_silhouettes = outline_cameras
+        .iter()
+        .any(OutlineSettings::renders_silhouettes);
 
-        let silhouette_entity = commands
-            .spawn((
-                SilhouetteMesh,
-                Mesh3d(mesh.0.clone()),
-                MeshMaterial3d(white_material.0.clone()),
-                Transform {
-                    translation,
-                    rotation,
-                    scale,
-                },
-                RenderLayers::layer(OUTLINE_RENDER_LAYER),
-            ))
-            .id();
+    // Add silhouette meshes for new outlined entities
+    for (entity, mesh, global_transform, range, skin, render_layers, no_culling, mesh_override) in
+        outlined.iter().filter(|_| needs_silhouettes)
+    {
+        let Some(object_id) = object_ids.allocate() else {
+            break;
+        };
+        // Draw the user's simplified proxy instead of the full mesh if given
+        let mesh = mesh_override.map_or(&mesh.0, |proxy| &proxy.0);
+
+        let silhouette_entity = spawn_silhouette(
+            &mut commands,
+            entity,
+            object_id,
+            &white_material,
+            global_transform,
+            outline_layer.silhouette_layers(render_layers),
+            mesh,
+            (range, skin, no_culling),
+        )
+        .id();
 
         // Mark the source entity as having a silhouette
         commands.entity(entity).insert(HasSilhouetteMesh {
             silhouette: silhouette_entity,
+            object_id,
         });
     }
 
     // Update silhouette transforms - O(n) by iterating changed sources directly
     for (source_entity, global_transform) in changed_sources.iter() {
-        if let Ok((_, has_silhouette)) = sources_with_silhouettes.get(source_entity) {
-            if let Ok((_, mut sil_transform)) = silhouettes.get_mut(has_silhouette.silhouette) {
-                let (scale, rotation, translation) = global_transform.to_scale_rotation_translation();
-                sil_transform.translation = translation;
-                sil_transform.rotation = rotation;
-                sil_transform.scale = scale;
+        if let Ok((_, has_silhouette, ..)) = sources_with_silhouettes.get(source_entity) {
+            if let Ok((mut sil_transform, mut sil_global)) =
+                silhouettes.get_mut(has_silhouette.silhouette)
+            {
+                // Write the GlobalTransform too, as propagation already ran this frame
+                *sil_transform = global_transform.compute_transform();
+                *sil_global = *global_transform;
             }
         }
     }
 
+    // Follow the LOD range, skin and culling of the source
+    let mirror: EntityHashSet = changed_mirrored
+        .iter()
+        .chain(removed_ranges.read())
+        .chain(removed_skins.read())
+        .chain(removed_no_culling.read())
+        .collect();
+    for entity in mirror {
+        if let Ok((_, has_silhouette, range, skin, no_culling)) =
+            sources_with_silhouettes.get(entity)
+        {
+            mirror_silhouette_source(
+                &mut commands.entity(has_silhouette.silhouette),
+                (range, skin, no_culling),
+            );
+        }
+    }
+
+    // Drop silhouettes of sources without a mesh; they are respawned by
+    // the `outlined` query once the mesh comes back
+    let mut mesh_removed = EntityHashSet::default();
+    for entity in removed_meshes.read() {
+        if let Ok((_, has_silhouette, ..)) = sources_with_silhouettes.get(entity) {
+            commands.entity(has_silhouette.silhouette).despawn();
+            object_ids.free(has_silhouette.object_id);
+            commands.entity(entity).remove::<HasSilhouetteMesh>();
+            mesh_removed.insert(entity);
+        }
+    }
+
     // Remove silhouette meshes for removed outlines
+    let mut despawned = EntityHashSet::default();
     for entity in removed.read() {
-        if let Ok((_, has_silhouette)) = sources_with_silhouettes.get(entity) {
+        // Already dropped along with its mesh
+        if mesh_removed.contains(&entity) {
+            continue;
+        }
+        if let Ok((_, has_silhouette, ..)) = sources_with_silhouettes.get(entity) {
             commands.entity(has_silhouette.silhouette).despawn();
+            object_ids.free(has_silhouette.object_id);
             // Remove HasSilhouetteMesh so outline can be re-added later
             commands.entity(entity).remove::<HasSilhouetteMesh>();
+        } else {
+            despawned.insert(entity);
+        }
+    }
+
+    // Sources that were despawned can only be found from their silhouettes
+    if !despawned.is_empty() {
+        for (silhouette, silhouette_mesh, tag) in all_silhouettes.iter() {
+            if despawned.contains(&silhouette_mesh.source) {
+                commands.entity(silhouette).despawn();
+                object_ids.free(tag.0);
+            }
+        }
+    }
+}
+
+/// Box mesh covering `aabb`, used as the silhouette of
+/// [`OutlineShape::BoundingBox`] outlines
+pub fn bounding_box_mesh(aabb: &Aabb) -> Mesh {
+    Mesh::from(Cuboid::from_size(Vec3::from(aabb.half_extents) * 2.0))
+        .translated_by(Vec3::from(aabb.center))
+}
+
+/// Bounds a bounding box silhouette was built from
+#[derive(Component, Clone, Copy, PartialEq)]
+pub struct SilhouetteBounds(pub Aabb);
+
+/// Points a silhouette at the mesh matching `shape`: the source mesh, or
+/// a box around the source's bounds
+///
+/// `source_mesh` is the mesh the silhouette should show for
+/// [`OutlineShape::Mesh`], i.e. the [`SilhouetteMeshOverride`] if any.
+pub fn apply_silhouette_shape(
+    silhouette: &mut EntityCommands,
+    meshes: &mut Assets<Mesh>,
+    shape: OutlineShape,
+    (source_mesh, aabb): (&Mesh3d, Option<&Aabb>),
+    (current_mesh, bounds): (&Mesh3d, Option<&SilhouetteBounds>),
+) {
+    match (shape, aabb) {
+        // Only rebuild the box when the bounds moved or it was replaced
+        (OutlineShape::BoundingBox, Some(aabb))
+            if current_mesh.0 == source_mesh.0 || bounds.is_none_or(|bounds| bounds.0 != *aabb) =>
+        {
+            silhouette.insert((
+                Mesh3d(meshes.add(bounding_box_mesh(aabb))),
+                SilhouetteBounds(*aabb),
+            ));
+        }
+        // The box is up to date
+        (OutlineShape::BoundingBox, Some(_)) => {}
+        _ if bounds.is_some() || current_mesh.0 != source_mesh.0 => {
+            silhouette
+                .insert(Mesh3d(source_mesh.0.clone()))
+                .remove::<SilhouetteBounds>();
+        }
+        _ => {}
+    }
+}
+
+/// Swaps silhouettes of [`OutlineShape::BoundingBox`] outlines to a box
+/// around the source's [`Aabb`]
+///
+/// Bounds are computed after the source mesh loads, so this also reacts to
+/// the [`Aabb`] appearing or changing. Sources with a
+/// [`SilhouetteMeshOverride`] get their proxy mesh instead of their own.
+pub fn sync_silhouette_shapes(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    sources: Query<(
+        &HasSilhouetteMesh,
+        &MeshOutline,
+        &Mesh3d,
+        Option<&Aabb>,
+        Option<&SilhouetteMeshOverride>,
+    )>,
+    changed: Query<
+        Entity,
+        (
+            With<HasSilhouetteMesh>,
+            Or<(
+                Added<HasSilhouetteMesh>,
+                Changed<MeshOutline>,
+                Changed<Mesh3d>,
+                Changed<Aabb>,
+                Changed<SilhouetteMeshOverride>,
+            )>,
+        ),
+    >,
+    mut removed_overrides: RemovedComponents<SilhouetteMeshOverride>,
+    silhouettes: Query<(&Mesh3d, Option<&SilhouetteBounds>), With<SilhouetteMesh>>,
+) {
+    for entity in changed.iter().chain(removed_overrides.read()) {
+        let Ok((has_silhouette, outline, mesh, aabb, mesh_override)) = sources.get(entity) else {
+            continue;
+        };
+        let Ok(current) = silhouettes.get(has_silhouette.silhouette) else {
+            continue;
+        };
+        let mesh = Mesh3d(mesh_override.map_or(&mesh.0, |proxy| &proxy.0).clone());
+        apply_silhouette_shape(
+            &mut commands.entity(has_silhouette.silhouette),
+            &mut meshes,
+            outline.shape,
+            (&mesh, aabb),
+            current,
+        );
+    }
+}
+
+/// Gives silhouettes of alpha-cutout or double-sided sources, or sources
+/// with an [`OutlineVertexShader`] or [`OutlineFragmentShader`], a matching
+/// material
+///
+/// Silhouettes whose sources share a material and shaders share the
+/// matching material too, so instances of a masked mesh keep batching like
+/// plain silhouettes do.
+///
+/// Silhouettes of fully transparent sources, such as the invisible proxy
+/// meshes of a glTF model outlined as a whole, are hidden so they don't
+/// widen the model's outline.
+///
+/// Only new silhouettes, sources whose material or shaders changed and
+/// silhouettes of modified source materials are updated, so UV animation on
+/// the source material (e.g. a sprite-sheet frame change) and runtime
+/// `cull_mode` changes are mirrored by the silhouette.
+pub fn sync_silhouette_masks(
+    mut commands: Commands,
+    white_material: Option<Res<SilhouetteWhiteMaterial>>,
+    source_materials: Res<Assets<StandardMaterial>>,
+    mut material_events: MessageReader<AssetEvent<StandardMaterial>>,
+    mut silhouette_materials: ResMut<Assets<SilhouetteMaterial>>,
+    sources: Query<&MeshMaterial3d<StandardMaterial>>,
+    shaders: Query<(Option<&OutlineVertexShader>, Option<&OutlineFragmentShader>)>,
+    changed_sources: Query<
+        Entity,
+        Or<(
+            Changed<MeshMaterial3d<StandardMaterial>>,
+            Changed<OutlineVertexShader>,
+            Changed<OutlineFragmentShader>,
+        )>,
+    >,
+    mut removed_materials: RemovedComponents<MeshMaterial3d<StandardMaterial>>,
+    mut removed_vertex_shaders: RemovedComponents<OutlineVertexShader>,
+    mut removed_fragment_shaders: RemovedComponents<OutlineFragmentShader>,
+    outlined_sources: Query<&HasSilhouetteMesh>,
+    selection: Res<OutlineSelection>,
+    new_silhouettes: Query<Entity, Added<SilhouetteMesh>>,
+    mut silhouettes: Query<(
+        Entity,
+        &SilhouetteMesh,
+        &MeshMaterial3d<SilhouetteMaterial>,
+        &mut Visibility,
+    )>,
+    // Matching material of each source material and shaders
+    mut shared: Local<
+        HashMap<
+            (
+                Option<AssetId<StandardMaterial>>,
+                Option<AssetId<Shader>>,
+                Option<AssetId<Image>>,
+                Option<AssetId<Shader>>,
+            ),
+            Handle<SilhouetteMaterial>,
+        >,
+    >,
+) {
+    let Some(white_material) = white_material else {
+        return;
+    };
+
+    // Source materials that loaded or changed, e.g. a new sprite-sheet frame
+    let mut changed_materials = HashSet::new();
+    for event in material_events.read() {
+        match event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => {
+                changed_materials.insert(*id);
+            }
+            AssetEvent::Removed { id } | AssetEvent::Unused { id } => {
+                shared.retain(|(material, ..), _| *material != Some(*id));
+            }
+        }
+    }
+
+    let mut dirty: EntityHashSet = new_silhouettes.iter().collect();
+    for source in changed_sources
+        .iter()
+        .chain(removed_materials.read())
+        .chain(removed_vertex_shaders.read())
+        .chain(removed_fragment_shaders.read())
+    {
+        let silhouette = outlined_sources
+            .get(source)
+            .map(|has_silhouette| has_silhouette.silhouette)
+            .ok()
+            .or_else(|| selection.silhouette(source));
+        dirty.extend(silhouette);
+    }
+    if !changed_materials.is_empty() {
+        for (entity, silhouette, ..) in silhouettes.iter() {
+            if sources
+                .get(silhouette.source)
+                .is_ok_and(|source| changed_materials.contains(&source.id()))
+            {
+                dirty.insert(entity);
+            }
+        }
+    }
+
+    for entity in dirty {
+        let Ok((_, silhouette, material, mut visibility)) = silhouettes.get_mut(entity) else {
+            continue;
+        };
+        let source_material_id = sources.get(silhouette.source).ok().map(|source| source.id());
+        let source_material = source_material_id.and_then(|id| source_materials.get(id));
+        let visible = !source_material.is_some_and(SilhouetteMaterial::is_invisible);
+        visibility.set_if_neq(if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+
+        let mask = source_material.and_then(SilhouetteMaterial::matching);
+        let (vertex, fragment) = shaders.get(silhouette.source).unwrap_or_default();
+        let mask = if vertex.is_some() || fragment.is_some() {
+            Some(SilhouetteMaterial {
+                vertex_shader: vertex.map(|vertex| vertex.shader.clone()),
+                vertex_texture: vertex.and_then(|vertex| vertex.texture.clone()),
+                fragment_shader: fragment.map(|fragment| fragment.0.clone()),
+                ..mask.unwrap_or_default()
+            })
+        } else {
+            mask
+        };
+
+        // Plain silhouettes share the white material
+        let Some(mask) = mask else {
+            if material.0 != white_material.0 {
+                commands
+                    .entity(entity)
+                    .insert(MeshMaterial3d(white_material.0.clone()));
+            }
+            continue;
+        };
+        let key = (
+            source_material_id,
+            vertex.map(|vertex| vertex.shader.id()),
+            vertex.and_then(|vertex| vertex.texture.as_ref().map(Handle::id)),
+            fragment.map(|fragment| fragment.0.id()),
+        );
+        let handle = shared
+            .entry(key)
+            .or_insert_with(|| silhouette_materials.add(mask.clone()));
+        // Only touch the asset when it changed to avoid re-uploading it
+        if silhouette_materials.get(&*handle) != Some(&mask) {
+            silhouette_materials.insert(&*handle, mask).ok();
+        }
+        if material.0 != *handle {
+            commands.entity(entity).insert(MeshMaterial3d(handle.clone()));
+        }
+    }
+}
+
+/// Moves silhouette meshes and cameras to their layers when
+/// [`OutlineRenderLayer`] or the [`RenderLayers`] of their sources or main
+/// cameras change
+///
+/// Silhouettes of sources with [`OutlineAllow`] or [`OutlineDeny`] go on
+/// the private layers of the cameras that outline them instead.
+pub fn sync_outline_render_layer(
+    outline_layer: Res<OutlineRenderLayer>,
+    changed_layers: Query<
+        (),
+        (
+            Or<(
+                Changed<RenderLayers>,
+                Changed<OutlineAllow>,
+                Changed<OutlineDeny>,
+            )>,
+            Without<SilhouetteMesh>,
+            Without<SilhouetteCamera>,
+        ),
+    >,
+    new_silhouettes: Query<&SilhouetteMesh, Added<SilhouetteMesh>>,
+    new_cameras: Query<(), Added<OutlineCameraLink>>,
+    mut removed_layers: RemovedComponents<RenderLayers>,
+    mut removed_allows: RemovedComponents<OutlineAllow>,
+    mut removed_denies: RemovedComponents<OutlineDeny>,
+    sources: Query<
+        (Option<&RenderLayers>, Option<&OutlineAllow>, Option<&OutlineDeny>),
+        (Without<SilhouetteMesh>, Without<SilhouetteCamera>),
+    >,
+    main_cameras: Query<
+        (Entity, &OutlineCameraLink, Option<&RenderLayers>),
+        (Without<SilhouetteMesh>, Without<SilhouetteCamera>),
+    >,
+    mut silhouettes: Query<(&SilhouetteMesh, &mut RenderLayers), Without<SilhouetteCamera>>,
+    mut silhouette_cameras: Query<&mut RenderLayers, (With<SilhouetteCamera>, Without<SilhouetteMesh>)>,
+) {
+    let removed = removed_layers.read().count()
+        + removed_allows.read().count()
+        + removed_denies.read().count()
+        > 0;
+    // New silhouettes spawn on their source's layers, which only need
+    // fixing up for filtered sources
+    let new_filtered = new_silhouettes.iter().any(|silhouette| {
+        sources
+            .get(silhouette.source)
+            .is_ok_and(|(_, allow, deny)| allow.is_some() || deny.is_some())
+    });
+    if !outline_layer.is_changed()
+        && changed_layers.is_empty()
+        && new_cameras.is_empty()
+        && !new_filtered
+        && !removed
+    {
+        return;
+    }
+
+    let default_layers = RenderLayers::default();
+    for (silhouette, mut layers) in silhouettes.iter_mut() {
+        let (source_layers, allow, deny) = sources.get(silhouette.source).unwrap_or_default();
+        let render_layers = if allow.is_none() && deny.is_none() {
+            outline_layer.silhouette_layers(source_layers)
+        } else {
+            let source_layers = source_layers.unwrap_or(&default_layers);
+            main_cameras
+                .iter()
+                .filter(|(camera, _, camera_layers)| {
+                    source_layers.intersects(camera_layers.unwrap_or(&default_layers))
+                        && allow.is_none_or(|allow| allow.0.contains(camera))
+                        && deny.is_none_or(|deny| !deny.0.contains(camera))
+                })
+                .map(|(_, link, _)| outline_layer.camera_layer(link.camera_index))
+                .collect()
+        };
+        if *layers != render_layers {
+            *layers = render_layers;
         }
     }
+
+    for (_, link, camera_layers) in main_cameras.iter() {
+        let Ok(mut layers) = silhouette_cameras.get_mut(link.silhouette_camera) else {
+            continue;
+        };
+        let render_layers =
+            outline_layer.silhouette_camera_layers(camera_layers, link.camera_index);
+        if *layers != render_layers {
+            *layers = render_layers;
+        }
+    }
+}
+
+/// Warns once for each entity other than a silhouette on a layer reserved
+/// for outlines
+///
+/// Such entities are drawn by silhouette cameras, or silhouettes show up in
+/// the entity's own camera, and either corrupts outlines. Every layer from
+/// [`OutlineRenderLayer`] up is reserved: the layers mapped from the app's
+/// layers as well as the per-camera layers.
+pub fn warn_outline_layer_conflicts(
+    outline_layer: Res<OutlineRenderLayer>,
+    entities: Query<
+        (Entity, Ref<RenderLayers>, Option<&Name>),
+        (Without<SilhouetteMesh>, Without<SilhouetteCamera>),
+    >,
+    mut warned: Local<EntityHashSet>,
+) {
+    for (entity, layers, name) in entities.iter() {
+        if !outline_layer.is_changed() && !layers.is_changed() {
+            continue;
+        }
+        let Some(layer) = layers.iter().find(|&layer| layer >= outline_layer.0) else {
+            continue;
+        };
+        if !warned.insert(entity) {
+            continue;
+        }
+        let name = name.map_or_else(|| entity.to_string(), |name| name.to_string());
+        warn!(
+            "{name} uses render layer {layer}, but layers from {} up are reserved for outline \
+             silhouettes and will corrupt outlines. Move it to a lower layer or raise \
+             OutlineRenderLayer.",
+            outline_layer.0
+        );
+    }
+}
+
+/// [`Camera::order`] of silhouette cameras: one below the lowest order of
+/// `cameras`, the app's cameras other than silhouette cameras
+///
+/// Silhouette cameras only read the scene and write their own textures, so
+/// they can all share this order. Rendering before every other camera keeps
+/// each one ahead of its main camera without tying with any camera whose
+/// output another depends on, like a portal camera one order below the view
+/// showing it.
+pub fn silhouette_camera_order<'a>(cameras: impl Iterator<Item = &'a Camera>) -> isize {
+    cameras
+        .map(|camera| camera.order)
+        .min()
+        .unwrap_or(0)
+        .saturating_sub(1)
 }
 
 /// Syncs silhouette camera transform with main camera
+///
+/// Runs after transform propagation and writes the silhouette camera's
+/// `GlobalTransform` directly, so camera cuts are picked up the same frame.
 pub fn sync_silhouette_cameras(
-    main_cameras: Query<(&Transform, &Projection, &OutlineCameraLink), Changed<Transform>>,
+    main_cameras: Query<(&GlobalTransform, &OutlineCameraLink), Changed<GlobalTransform>>,
+    changed_settings: Query<(&OutlineSettings, &OutlineCameraLink), Changed<OutlineSettings>>,
+    changed_orders: Query<(), (Changed<Camera>, Without<SilhouetteCamera>)>,
+    other_cameras: Query<&Camera, Without<SilhouetteCamera>>,
     mut silhouette_cameras: Query<
-        (&mut Transform, &mut Projection),
+        (&mut Transform, &mut GlobalTransform, &mut Camera),
         (With<SilhouetteCamera>, Without<OutlineCameraLink>),
     >,
 ) {
-    for (main_transform, main_projection, link) in main_cameras.iter() {
-        if let Ok((mut sil_transform, mut sil_projection)) =
+    for (main_global, link) in main_cameras.iter() {
+        if let Ok((mut sil_transform, mut sil_global, _)) =
             silhouette_cameras.get_mut(link.silhouette_camera)
         {
-            *sil_transform = *main_transform;
+            *sil_transform = main_global.compute_transform();
+            *sil_global = *main_global;
+        }
+    }
+
+    // Stop rendering silhouettes for cameras switched to depth edges
+    for (settings, link) in changed_settings.iter() {
+        if let Ok((_, _, mut sil_camera)) = silhouette_cameras.get_mut(link.silhouette_camera) {
+            sil_camera.is_active = settings.mode == OutlineMode::Silhouette;
+        }
+    }
+
+    // Keep rendering before every other camera when cameras are reordered
+    if !changed_orders.is_empty() {
+        let order = silhouette_camera_order(other_cameras.iter());
+        for (_, _, mut sil_camera) in silhouette_cameras.iter_mut() {
+            if sil_camera.order != order {
+                sil_camera.order = order;
+            }
+        }
+    }
+}
+
+/// Syncs silhouette camera projection with main camera
+///
+/// Runs before camera matrices are updated so projection changes apply the
+/// same frame. The projection is cloned as a whole, so
+/// [`Projection::Custom`] projections are replicated too.
+pub fn sync_silhouette_projections(
+    main_cameras: Query<(&Projection, &OutlineCameraLink), Changed<Projection>>,
+    mut silhouette_projections: Query<
+        &mut Projection,
+        (With<SilhouetteCamera>, Without<OutlineCameraLink>),
+    >,
+) {
+    for (main_projection, link) in main_cameras.iter() {
+        if let Ok(mut sil_projection) = silhouette_projections.get_mut(link.silhouette_camera) {
             *sil_projection = main_projection.clone();
         }
     }
 }
 
+/// Outline strength and object ID history texture, written by the composite
+/// pass at view size
+fn new_history_image(view_size: UVec2) -> Image {
+    let mut history_image = Image::new_fill(
+        Extent3d {
+            width: view_size.x.max(1),
+            height: view_size.y.max(1),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0; 8], // 4 x f16 = 8 bytes
+        TextureFormat::Rgba16Float,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    history_image.texture_descriptor.usage =
+        TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING;
+    history_image
+}
+
 /// Resizes silhouette and JFA textures when the window size changes
+///
+/// Checks the render target every frame, so a camera switched to a larger
+/// image target, e.g. to capture a high-resolution screenshot, gets
+/// full-resolution outline textures the same frame.
+///
+/// Also allocates the history textures when
+/// [`OutlineSettings::temporal_smoothing`] is turned on, and frees them
+/// when it is turned off.
 pub fn resize_silhouette_textures(
     mut images: ResMut<Assets<Image>>,
-    cameras: Query<(Option<&RenderTarget>, &OutlineCameraLink), With<OutlineSettings>>,
+    mut cameras: Query<
+        (
+            &Camera,
+            Option<&RenderTarget>,
+            Option<&OutlineTargetSize>,
+            &OutlineSettings,
+            &mut OutlineCameraLink,
+        ),
+        Without<SilhouetteCamera>,
+    >,
+    mut silhouette_cameras: Query<&mut Camera, With<SilhouetteCamera>>,
     windows: Query<&Window>,
 ) {
-    for (render_target, link) in cameras.iter() {
-        // Get current window size
-        let target_size = match render_target {
-            Some(RenderTarget::Window(window_ref)) => {
-                let window = match window_ref {
-                    bevy::window::WindowRef::Primary => windows.iter().next(),
-                    bevy::window::WindowRef::Entity(e) => windows.get(*e).ok(),
-                };
-                window.map(|w| UVec2::new(w.physical_width(), w.physical_height()))
-            }
-            Some(RenderTarget::Image(image_target)) => {
-                images.get(&image_target.handle).map(|img| img.size())
-            }
-            _ => windows
-                .iter()
-                .next()
-                .map(|w| UVec2::new(w.physical_width(), w.physical_height())),
-        };
-
-        let Some(target_size) = target_size else {
+    for (camera, render_target, target_size_override, settings, mut link) in cameras.iter_mut() {
+        // Get current target size
+        let Some(view_size) =
+            outline_texture_size(camera, render_target, target_size_override, &windows, &images)
+        else {
             continue;
         };
 
         // Skip if size is zero
-        if target_size.x == 0 || target_size.y == 0 {
+        if view_size.x == 0 || view_size.y == 0 {
             continue;
         }
 
+        // Widen the silhouette camera's view by the overscan margin
+        let margin = settings.overscan_margin();
+        let target_size = view_size + UVec2::splat(2 * margin);
+        if let Ok(mut sil_camera) = silhouette_cameras.get_mut(link.silhouette_camera) {
+            let sub_view = overscan_sub_view(view_size, margin);
+            if sil_camera.sub_camera_view != sub_view {
+                sil_camera.sub_camera_view = sub_view;
+            }
+        }
+
         let extent = Extent3d {
             width: target_size.x,
             height: target_size.y,
@@ -380,18 +1686,190 @@ pub fn resize_silhouette_textures(
                 }
             }
         }
+
+        let smoothing = settings.temporal_smoothing > 0.0;
+        if smoothing != link.history_textures.is_some() {
+            link.history_textures =
+                smoothing.then(|| [(); 2].map(|_| images.add(new_history_image(view_size))));
+        }
+
+        // Resize history textures, which cover the view without overscan
+        for history_texture in link.history_textures.iter().flatten() {
+            if let Some(history_image) = images.get(history_texture) {
+                if history_image.size() != view_size {
+                    if let Some(img) = images.get_mut(history_texture) {
+                        img.resize(Extent3d {
+                            width: view_size.x,
+                            height: view_size.y,
+                            depth_or_array_layers: 1,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Resize the custom compositing texture, which also covers the view
+        if let Some(outline_image) = images.get(&link.outline_texture) {
+            if outline_image.size() != view_size {
+                if let Some(img) = images.get_mut(&link.outline_texture) {
+                    img.resize(Extent3d {
+                        width: view_size.x,
+                        height: view_size.y,
+                        depth_or_array_layers: 1,
+                    });
+                }
+            }
+        }
     }
 }
 
+/// Stage the outline is actually drawn at on a camera with `msaa`
+///
+/// The transparent pass resolves its multisampled texture over the whole
+/// view, erasing an outline drawn before it, so multisampled cameras fall
+/// back to drawing before tonemapping. MSAA writeback between cameras needs
+/// no special handling at the later stages, as it copies the main texture
+/// the outline was already composited into.
+fn effective_stage(stage: OutlineStage, msaa: Msaa) -> OutlineStage {
+    if stage == OutlineStage::BeforeTransparent && msaa.samples() > 1 {
+        warn_once!(
+            "OutlineStage::BeforeTransparent requires Msaa::Off, drawing outlines before tonemapping instead"
+        );
+        return OutlineStage::BeforeTonemapping;
+    }
+    stage
+}
+
+/// Whether any of the outline's colors is brighter than an SDR target holds
+fn is_overbright(outline: &MeshOutline) -> bool {
+    let overbright = |color: LinearRgba| color.red.max(color.green).max(color.blue) > 1.0;
+    overbright(outline.color) || outline.fill.is_some_and(overbright)
+}
+
 /// Extract outline data to render world
+///
+/// Render world views persist across frames, so cameras without anything to
+/// outline have their [`ExtractedOutlineData`] removed rather than left
+/// stale, and the outline node skips them. This covers frames without any
+/// outlined entity as well as cameras whose silhouette camera sees none.
 pub fn extract_outline_data(
     mut commands: Commands,
-    cameras: Extract<Query<(Entity, &OutlineCameraLink, &OutlineSettings)>>,
+    cameras: Extract<
+        Query<(
+            Entity,
+            &OutlineCameraLink,
+            &OutlineSettings,
+            Option<&RenderLayers>,
+            Has<Hdr>,
+            &Projection,
+            &Camera,
+        )>,
+    >,
+    silhouette_cameras: Extract<Query<&VisibleEntities, With<SilhouetteCamera>>>,
+    depth_edge_cameras: Extract<
+        Query<(
+            Entity,
+            &OutlineSettings,
+            &Projection,
+            Option<&Msaa>,
+            Option<&RenderLayers>,
+        )>,
+    >,
+    camera_layer: Extract<Res<OutlineCameraLayer>>,
     outlines: Extract<Query<&MeshOutline>>,
+    outlined_objects: Extract<
+        Query<(
+            Entity,
+            &HasSilhouetteMesh,
+            &MeshOutline,
+            Option<&Aabb>,
+            &GlobalTransform,
+            Has<OutlineHoverable>,
+        )>,
+    >,
+    selection: Extract<Res<OutlineSelection>>,
+    hover: Extract<Res<OutlineHover>>,
+    images: Extract<Res<Assets<Image>>>,
+    time: Extract<Res<Time>>,
+    frame_count: Extract<Res<FrameCount>>,
     render_entity_lookup: Extract<Query<&bevy::render::sync_world::RenderEntity>>,
+    mut object_styles: ResMut<ExtractedOutlineObjectStyles>,
+    // Last frame each camera's outlines wrote history
+    mut history_frames: Local<EntityHashMap<u32>>,
+    // Stage and whether depth edges were drawn last extracted for each
+    // view, with the frame it was last seen in
+    mut extracted_modes: Local<EntityHashMap<(OutlineStage, bool, u32)>>,
 ) {
+    // Forget cameras that were despawned or stopped drawing outlines
+    history_frames.retain(|_, frame| frame.wrapping_add(1) >= frame_count.0);
+    extracted_modes.retain(|_, (.., frame)| frame.wrapping_add(1) >= frame_count.0);
+
+    // Depth edge cameras don't depend on any outlined entities
+    for (entity, settings, projection, msaa, render_layers) in depth_edge_cameras.iter() {
+        let Ok(render_entity) = render_entity_lookup.get(entity) else {
+            continue;
+        };
+        // Only touch the view when its stage or mode changed
+        let stage = effective_stage(settings.stage, msaa.copied().unwrap_or_default());
+        let depth_edges = matches!(settings.mode, OutlineMode::DepthEdges(_));
+        let previous =
+            extracted_modes.insert(render_entity.id(), (stage, depth_edges, frame_count.0));
+        let (stage_changed, mode_changed) = previous
+            .map_or((true, true), |(old_stage, old_mode, _)| {
+                (old_stage != stage, old_mode != depth_edges)
+            });
+        if stage_changed {
+            commands.entity(render_entity.id()).insert(stage);
+        }
+        let OutlineMode::DepthEdges(edges) = settings.mode else {
+            if mode_changed {
+                commands
+                    .entity(render_entity.id())
+                    .remove::<(ExtractedDepthEdgeData, DepthEdgeRenderResources)>();
+            }
+            continue;
+        };
+        if mode_changed {
+            commands
+                .entity(render_entity.id())
+                .remove::<ExtractedOutlineData>();
+        }
+
+        let perspective_near = match projection {
+            Projection::Perspective(perspective) => perspective.near,
+            _ => 0.0,
+        };
+
+        commands
+            .entity(render_entity.id())
+            .insert(ExtractedDepthEdgeData {
+                settings: DepthEdgeShaderSettings {
+                    color: edges.color.to_f32_array(),
+                    width: if settings.enabled && camera_layer.includes(render_layers) {
+                        edges.width
+                    } else {
+                        0.0
+                    },
+                    depth_threshold: edges.depth_threshold,
+                    normal_threshold: edges.normal_threshold,
+                    perspective_near,
+                    premultiplied_alpha: if settings.premultiplied_alpha { 1.0 } else { 0.0 },
+                    _padding: [0.0; 3],
+                },
+            });
+    }
+
     // Early exit if no outlined entities - skip all rendering
-    let Some(first_outline) = outlines.iter().next() else {
+    let selection_outline = selection.effective_style();
+    let selection_style = (!selection.entities.is_empty()).then_some(&selection_outline);
+    let Some(first_outline) = outlines.iter().next().or(selection_style) else {
+        for (entity, ..) in cameras.iter() {
+            if let Ok(render_entity) = render_entity_lookup.get(entity) {
+                commands
+                    .entity(render_entity.id())
+                    .remove::<ExtractedOutlineData>();
+            }
+        }
         return;
     };
 
@@ -401,25 +1879,213 @@ pub fn extract_outline_data(
         first_outline.color.blue,
         first_outline.color.alpha,
     ];
-    let width = first_outline.width;
 
-    for (entity, link, settings) in cameras.iter() {
+    // Per-object styles, so objects sharing a mesh can still differ. The
+    // vector is refilled in place to keep its allocation
+    let object_styles = &mut object_styles.0;
+    object_styles.clear();
+    let mut any_fill = false;
+    let mut any_overbright = false;
+    let mut any_outline = false;
+    // Widest outline in each unit, indexed like `OutlineObjectStyle::width_unit`,
+    // to size the flood for every object on every camera
+    let mut max_widths = [
+        (WidthUnit::Pixels, Vec2::ZERO),
+        (WidthUnit::ViewportFraction, Vec2::ZERO),
+        (WidthUnit::LogicalPixels, Vec2::ZERO),
+    ];
+    let styled_objects = outlined_objects
+        .iter()
+        .map(|(entity, has_silhouette, outline, aabb, transform, hoverable)| {
+            // Bounding sphere, so tiny objects can keep their outline in proportion
+            let radius = aabb.map_or(0.0, |aabb| {
+                Vec3::from(aabb.half_extents).length() * transform.scale().abs().max_element()
+            });
+            // Hoverables are only pickable until hovered
+            let pick_only = hoverable && hover.hovered != Some(entity);
+            (has_silhouette.object_id, outline, radius, pick_only)
+        })
+        .chain(
+            selection
+                .object_ids()
+                .map(|id| (id, &selection_outline, 0.0, false)),
+        );
+    for (object_id, outline, radius, pick_only) in styled_objects {
+        let index = object_id as usize;
+        if object_styles.len() <= index {
+            object_styles.resize(index + 1, OutlineObjectStyle::new(first_outline));
+        }
+        let style = OutlineObjectStyle {
+            radius,
+            pick_only: if pick_only { 1.0 } else { 0.0 },
+            ..OutlineObjectStyle::new(outline)
+        };
+        object_styles[index] = style;
+        if pick_only {
+            continue;
+        }
+        let max_width = &mut max_widths[style.width_unit as usize].1;
+        *max_width = max_width.max(outline.width);
+        any_outline = true;
+        any_fill |= outline.fill.is_some() || outline.hatch.is_some();
+        any_overbright |= is_overbright(outline);
+    }
+
+    for (entity, link, settings, render_layers, hdr, projection, camera) in cameras.iter() {
+        if settings.mode != OutlineMode::Silhouette {
+            continue;
+        }
+
         // Get the render entity for this camera
         let Ok(render_entity) = render_entity_lookup.get(entity) else {
             continue;
         };
 
+        // Skip all passes while no silhouette is in view
+        let sees_silhouettes = silhouette_cameras
+            .get(link.silhouette_camera)
+            .is_ok_and(|visible| !visible.is_empty(TypeId::of::<Mesh3d>()));
+        if !sees_silhouettes {
+            commands
+                .entity(render_entity.id())
+                .remove::<ExtractedOutlineData>();
+            continue;
+        }
+
+        if any_overbright && !hdr && settings.enabled {
+            warn_once!(
+                "Outline colors brighter than 1.0 are clamped on cameras without Hdr; add Hdr to the camera for bright outlines"
+            );
+        }
+
+        // Resolve the width against the outline texture the JFA runs on
+        let Some(image) = images.get(&link.silhouette_texture) else {
+            continue;
+        };
+        let margin = settings.overscan_margin();
+        let view_height = image.height().saturating_sub(2 * margin);
+        let scale_factor = camera.target_scaling_factor().unwrap_or(1.0);
+        let width = max_widths.iter().fold(Vec2::ZERO, |width, (unit, max_width)| {
+            width.max(unit.to_pixels(*max_width, view_height, scale_factor))
+        });
+
+        // Pixels per world unit, to size objects' bounds on screen
+        let (size_scale, perspective_near, orthographic_depth) = match projection {
+            Projection::Perspective(perspective) => (
+                view_height as f32 * 0.5 / (perspective.fov * 0.5).tan(),
+                perspective.near,
+                0.0,
+            ),
+            Projection::Orthographic(orthographic) if orthographic.area.height() > 0.0 => (
+                view_height as f32 / orthographic.area.height(),
+                0.0,
+                orthographic.far - orthographic.near,
+            ),
+            _ => (0.0, 0.0, 0.0),
+        };
+
+        // History textures swap roles every frame. History only holds last
+        // frame's outlines if they were drawn, not if they stopped for a
+        // while or smoothing was just turned on
+        let history_write = frame_count.0 as usize % 2;
+        let history_textures = link.history_textures.as_ref().map(|textures| {
+            [
+                textures[1 - history_write].clone(),
+                textures[history_write].clone(),
+            ]
+        });
+        let history_valid = history_frames
+            .get(&entity)
+            .is_some_and(|&frame| frame.wrapping_add(1) == frame_count.0);
+        if history_textures.is_some() && any_outline {
+            history_frames.insert(entity, frame_count.0);
+        }
+
+        // Objects' widths are clamped to this in the shader
+        let width = width.min(Vec2::splat(settings.max_width as f32));
+        // Effects reading the distance field need the Jump Flood
+        let dilate = !settings.distance_field && width.max_element() <= THIN_OUTLINE_WIDTH;
+
         commands.entity(render_entity.id()).insert(ExtractedOutlineData {
             silhouette_texture: link.silhouette_texture.clone(),
             jfa_ping_texture: link.jfa_ping_texture.clone(),
             jfa_pong_texture: link.jfa_pong_texture.clone(),
+            history_textures,
+            outline_texture: (!settings.auto_composite).then(|| link.outline_texture.clone()),
+            jfa_width: if settings.distance_field {
+                settings.max_width
+            } else {
+                (width.max_element().ceil() as u32).min(settings.max_width)
+            },
+            jfa_schedule: settings.jfa_schedule,
+            draws_outlines: any_outline,
             settings: OutlineShaderSettings {
                 color,
+                region: settings.region.map_or([0.0, 0.0, 1.0, 1.0], |region| {
+                    [region.min.x, region.min.y, region.max.x, region.max.y]
+                }),
                 width,
-                enabled: if settings.enabled { 1.0 } else { 0.0 },
-                _padding: [0.0; 2],
+                enabled: if settings.enabled && camera_layer.includes(render_layers) {
+                    1.0
+                } else {
+                    0.0
+                },
+                auto_contrast: if settings.auto_contrast { 1.0 } else { 0.0 },
+                near_fade_distance: settings.near_fade_distance,
+                dither_opacity: settings
+                    .dither_opacity
+                    .map_or(-1.0, |opacity| opacity.clamp(0.0, 1.0)),
+                overscan: margin as f32,
+                time: time.elapsed_secs_wrapped(),
+                temporal_smoothing: if history_valid {
+                    settings.temporal_smoothing.clamp(0.0, 1.0)
+                } else {
+                    0.0
+                },
+                view_height: view_height as f32,
+                scale_factor,
+                fill: if any_fill { 1.0 } else { 0.0 },
+                // There is no scene alpha to keep without auto compositing
+                preserve_alpha: if settings.preserve_alpha && settings.auto_composite {
+                    1.0
+                } else {
+                    0.0
+                },
+                // The outline texture is always premultiplied
+                premultiplied_alpha: if settings.premultiplied_alpha || !settings.auto_composite {
+                    1.0
+                } else {
+                    0.0
+                },
+                size_scale,
+                perspective_near,
+                orthographic_depth,
+                dilate: if dilate { 1.0 } else { 0.0 },
+                detached: if settings.auto_composite { 0.0 } else { 1.0 },
+                _padding: 0.0,
             },
         });
+
+        if !any_outline {
+            continue;
+        }
+
+        // Expose the outlines for custom compositing
+        if !settings.auto_composite {
+            commands.entity(render_entity.id()).insert(OutlineTexture {
+                texture: link.outline_texture.clone(),
+                frame: frame_count.0,
+            });
+        }
+
+        // Expose the field for reuse by other effects this frame
+        let Some(texture) = link.distance_field(settings) else {
+            continue;
+        };
+        commands.entity(render_entity.id()).insert(OutlineDistanceField {
+            texture: texture.clone(),
+            frame: frame_count.0,
+        });
     }
 }
 
@@ -430,13 +2096,10 @@ pub fn prepare_outline_resources(
     render_queue: Res<RenderQueue>,
     outline_pipeline: Res<OutlinePipeline>,
     gpu_images: Res<RenderAssets<GpuImage>>,
-    query: Query<(Entity, &ExtractedOutlineData, Option<&OutlineRenderResources>)>,
+    mut query: Query<(Entity, &ExtractedOutlineData, Option<&mut OutlineRenderResources>)>,
 ) {
-    for (entity, outline_data, existing_resources) in query.iter() {
+    for (entity, outline_data, existing_resources) in query.iter_mut() {
         // Get GPU textures
-        let Some(silhouette_gpu) = gpu_images.get(&outline_data.silhouette_texture) else {
-            continue;
-        };
         let Some(jfa_ping_gpu) = gpu_images.get(&outline_data.jfa_ping_texture) else {
             continue;
         };
@@ -446,11 +2109,13 @@ pub fn prepare_outline_resources(
 
         let tex_width = jfa_ping_gpu.texture.width();
         let tex_height = jfa_ping_gpu.texture.height();
-        let width = outline_data.settings.width;
+        // Outlines growing or shrinking only change the passes when they
+        // cross a power of two
+        let step_sizes = outline_data.jfa_schedule.step_sizes(outline_data.jfa_width);
 
         // Check if we can reuse existing resources
-        if let Some(existing) = existing_resources {
-            if existing.cached_width == width
+        if let Some(mut existing) = existing_resources {
+            if existing.cached_step_sizes == step_sizes
                 && existing.cached_texture_size == (tex_width, tex_height)
             {
                 // Only update settings buffer if settings actually changed
@@ -460,6 +2125,7 @@ pub fn prepare_outline_resources(
                         0,
                         bytemuck::bytes_of(&outline_data.settings),
                     );
+                    existing.cached_settings = outline_data.settings;
                 }
                 continue;
             }
@@ -473,27 +2139,14 @@ pub fn prepare_outline_resources(
             .texture
             .create_view(&TextureViewDescriptor::default());
 
-        // Calculate pass count
-        let actual_width = width.ceil() as u32;
-        let pass_count = if actual_width > 0 {
-            ((actual_width as f32).log2().ceil() as u32).max(1)
-        } else {
-            0
-        };
-
-        // Create init bind group
-        let init_bind_group = render_device.create_bind_group(
-            "jfa_init_compute_bind_group",
-            &outline_pipeline.init_layout,
-            &BindGroupEntries::sequential((&silhouette_gpu.texture_view, &ping_view)),
-        );
+        let pass_count = step_sizes.len();
 
         // Create step buffers and bind groups
-        let mut step_buffers = Vec::with_capacity(pass_count as usize);
-        let mut step_bind_groups = Vec::with_capacity(pass_count as usize);
+        let mut step_buffers = Vec::with_capacity(pass_count);
+        let mut step_bind_groups = Vec::with_capacity(pass_count);
 
-        for pass_idx in 0..pass_count {
-            let step_size = (actual_width >> (pass_idx + 1)).max(1) as f32;
+        for (pass_idx, &step_size) in step_sizes.iter().enumerate() {
+            let step_size = step_size as f32;
             let read_from_ping = pass_idx % 2 == 0;
 
             let (input_view, output_view) = if read_from_ping {
@@ -523,30 +2176,132 @@ pub fn prepare_outline_resources(
                 )),
             );
 
-            step_buffers.push(step_buffer);
-            step_bind_groups.push(step_bind_group);
+            step_buffers.push(step_buffer);
+            step_bind_groups.push(step_bind_group);
+        }
+
+        // Create settings buffer
+        let settings_buffer = render_device.create_buffer_with_data(
+            &bevy::render::render_resource::BufferInitDescriptor {
+                label: Some("outline_settings_buffer"),
+                contents: bytemuck::bytes_of(&outline_data.settings),
+                usage: bevy::render::render_resource::BufferUsages::UNIFORM
+                    | bevy::render::render_resource::BufferUsages::COPY_DST,
+            },
+        );
+
+        commands.entity(entity).insert(OutlineRenderResources {
+            ping_view,
+            pong_view,
+            step_bind_groups,
+            step_buffers,
+            settings_buffer,
+            cached_step_sizes: step_sizes,
+            cached_texture_size: (tex_width, tex_height),
+            cached_settings: outline_data.settings,
+        });
+    }
+}
+
+/// Prepare system that uploads per-object outline styles, growing the
+/// storage buffer when more objects are outlined
+///
+/// Only styles that differ from the last upload go to the GPU, one write
+/// per run of neighbouring changed objects, so restyling one object among
+/// thousands uploads a single style.
+pub fn prepare_outline_object_styles(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    object_styles: Res<ExtractedOutlineObjectStyles>,
+    style_buffer: Option<ResMut<OutlineObjectStyleBuffer>>,
+) {
+    // Storage buffers can't be empty
+    let styles: &[OutlineObjectStyle] = if object_styles.0.is_empty() {
+        &[OutlineObjectStyle::default()]
+    } else {
+        &object_styles.0
+    };
+
+    if let Some(mut style_buffer) = style_buffer {
+        if style_buffer.uploaded == styles {
+            return;
+        }
+        if style_buffer.capacity >= styles.len() {
+            let uploaded = &style_buffer.uploaded;
+            let changed = |index: usize| uploaded.get(index) != Some(&styles[index]);
+            let mut index = 0;
+            while index < styles.len() {
+                if !changed(index) {
+                    index += 1;
+                    continue;
+                }
+                let start = index;
+                while index < styles.len() && changed(index) {
+                    index += 1;
+                }
+                render_queue.write_buffer(
+                    &style_buffer.buffer,
+                    (start * size_of::<OutlineObjectStyle>()) as u64,
+                    bytemuck::cast_slice(&styles[start..index]),
+                );
+            }
+            style_buffer.uploaded = styles.to_vec();
+            return;
+        }
+    }
+
+    // Leave room to grow so new objects don't reallocate every frame
+    let capacity = styles.len().next_power_of_two();
+    let mut contents = styles.to_vec();
+    contents.resize(capacity, OutlineObjectStyle::default());
+    let buffer = render_device.create_buffer_with_data(
+        &bevy::render::render_resource::BufferInitDescriptor {
+            label: Some("outline_object_styles_buffer"),
+            contents: bytemuck::cast_slice(&contents),
+            usage: bevy::render::render_resource::BufferUsages::STORAGE
+                | bevy::render::render_resource::BufferUsages::COPY_DST,
+        },
+    );
+    commands.insert_resource(OutlineObjectStyleBuffer {
+        buffer,
+        capacity,
+        uploaded: styles.to_vec(),
+    });
+}
+
+/// Prepare system that creates/updates the settings buffer for depth edge rendering
+pub fn prepare_depth_edge_resources(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut query: Query<(Entity, &ExtractedDepthEdgeData, Option<&mut DepthEdgeRenderResources>)>,
+) {
+    for (entity, edge_data, existing_resources) in query.iter_mut() {
+        if let Some(mut existing) = existing_resources {
+            if existing.cached_settings != edge_data.settings {
+                render_queue.write_buffer(
+                    &existing.settings_buffer,
+                    0,
+                    bytemuck::bytes_of(&edge_data.settings),
+                );
+                existing.cached_settings = edge_data.settings;
+            }
+            continue;
         }
 
-        // Create settings buffer
         let settings_buffer = render_device.create_buffer_with_data(
             &bevy::render::render_resource::BufferInitDescriptor {
-                label: Some("outline_settings_buffer"),
-                contents: bytemuck::bytes_of(&outline_data.settings),
+                label: Some("depth_edge_settings_buffer"),
+                contents: bytemuck::bytes_of(&edge_data.settings),
                 usage: bevy::render::render_resource::BufferUsages::UNIFORM
                     | bevy::render::render_resource::BufferUsages::COPY_DST,
             },
         );
 
-        commands.entity(entity).insert(OutlineRenderResources {
-            ping_view,
-            pong_view,
-            init_bind_group,
-            step_bind_groups,
-            step_buffers,
+        commands.entity(entity).insert(DepthEdgeRenderResources {
             settings_buffer,
-            cached_width: width,
-            cached_texture_size: (tex_width, tex_height),
-            cached_settings: outline_data.settings,
+            cached_settings: edge_data.settings,
         });
     }
 }
@@ -566,8 +2321,24 @@ pub struct OutlinePipeline {
     pub composite_layout: BindGroupLayout,
     pub composite_pipeline_id: CachedRenderPipelineId,
     pub composite_pipeline_id_hdr: CachedRenderPipelineId,
+    // Variants writing outline strength history for temporal smoothing
+    pub composite_pipeline_id_smoothed: CachedRenderPipelineId,
+    pub composite_pipeline_id_hdr_smoothed: CachedRenderPipelineId,
+
+    // Depth edge pass - fragment shader
+    pub depth_edge_layout: BindGroupLayout,
+    pub depth_edge_pipeline_id: CachedRenderPipelineId,
+    pub depth_edge_pipeline_id_hdr: CachedRenderPipelineId,
+
+    // Hover pick pass - COMPUTE shader
+    pub hover_pick_layout: BindGroupLayout,
+    pub hover_pick_pipeline_id: CachedComputePipelineId,
 
     pub sampler: Sampler,
+    /// Bound instead of the depth prepass when the view has no
+    /// single-sampled one. Its zero depth is the far plane, so it masks
+    /// nothing
+    pub fallback_depth: TextureView,
 }
 
 impl FromWorld for OutlinePipeline {
@@ -576,7 +2347,28 @@ impl FromWorld for OutlinePipeline {
         let asset_server = world.resource::<AssetServer>();
         let pipeline_cache = world.resource::<PipelineCache>();
 
-        let sampler = render_device.create_sampler(&SamplerDescriptor::default());
+        // Clamp explicitly so seeds near one screen edge never wrap to the other
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("outline_sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            ..default()
+        });
+
+        // Textures are zero-initialized, i.e. cleared to the far plane
+        let fallback_depth = render_device
+            .create_texture(&TextureDescriptor {
+                label: Some("outline_fallback_depth"),
+                size: Extent3d::default(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Depth32Float,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+            .create_view(&TextureViewDescriptor::default());
 
         // Shaders
         let vertex_shader = asset_server
@@ -595,6 +2387,8 @@ impl FromWorld for OutlinePipeline {
                 texture_2d(TextureSampleType::Float { filterable: false }),
                 // Output texture (write)
                 texture_storage_2d(TextureFormat::Rg16Unorm, StorageTextureAccess::WriteOnly),
+                // Per-object outline styles, to skip pick-only objects
+                storage_buffer_read_only_sized(false, None),
             ),
         );
 
@@ -650,6 +2444,37 @@ impl FromWorld for OutlinePipeline {
             zero_initialize_workgroup_memory: false,
         });
 
+        // ========== Hover Pick Compute Pipeline ==========
+        let hover_pick_shader = asset_server.load("embedded://bevy_outliner/shaders/hover_pick.wgsl");
+
+        let hover_pick_layout_entries = BindGroupLayoutEntries::sequential(
+            ShaderStages::COMPUTE,
+            (
+                // Silhouette texture (read)
+                texture_2d(TextureSampleType::Float { filterable: false }),
+                // Cursor texel in, picked object ID out
+                storage_buffer_sized(false, None),
+            ),
+        );
+
+        let hover_pick_layout = render_device.create_bind_group_layout(
+            Some("hover_pick_bind_group_layout"),
+            &hover_pick_layout_entries,
+        );
+
+        let hover_pick_pipeline_id = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("hover_pick_pipeline".into()),
+            layout: vec![BindGroupLayoutDescriptor::new(
+                "hover_pick_bind_group_layout",
+                &hover_pick_layout_entries,
+            )],
+            shader: hover_pick_shader,
+            shader_defs: vec![],
+            entry_point: Some("main".into()),
+            push_constant_ranges: vec![],
+            zero_initialize_workgroup_memory: false,
+        });
+
         // ========== Composite Pipeline ==========
         let composite_layout_entries = BindGroupLayoutEntries::sequential(
             ShaderStages::FRAGMENT,
@@ -668,6 +2493,12 @@ impl FromWorld for OutlinePipeline {
                 sampler_layout(SamplerBindingType::Filtering),
                 // Settings uniform
                 uniform_buffer::<OutlineShaderSettings>(false),
+                // Previous frame's outline strength
+                texture_2d(TextureSampleType::Float { filterable: true }),
+                // Per-object outline styles
+                storage_buffer_read_only_sized(false, None),
+                // Depth prepass, masking backlight halos
+                texture_depth_2d(),
             ),
         );
 
@@ -681,9 +2512,28 @@ impl FromWorld for OutlinePipeline {
             &composite_layout_entries,
         );
 
-        let composite_pipeline_id =
+        // Outline strength and object written for the next frame's temporal smoothing
+        let history_target = ColorTargetState {
+            format: TextureFormat::Rgba16Float,
+            blend: None,
+            write_mask: ColorWrites::ALL,
+        };
+
+        // One pipeline per view format, with a variant also writing the
+        // outline strength for the next frame's temporal smoothing
+        let queue_composite_pipeline = |label: &'static str, format: TextureFormat, smoothed: bool| {
+            let mut targets = vec![Some(ColorTargetState {
+                format,
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            })];
+            let mut shader_defs = vec![];
+            if smoothed {
+                targets.push(Some(history_target.clone()));
+                shader_defs.push("TEMPORAL_SMOOTHING".into());
+            }
             pipeline_cache.queue_render_pipeline(RenderPipelineDescriptor {
-                label: Some("jfa_composite_pipeline".into()),
+                label: Some(label.into()),
                 layout: vec![composite_layout_desc.clone()],
                 vertex: bevy::render::render_resource::VertexState {
                     shader: vertex_shader.clone(),
@@ -693,6 +2543,80 @@ impl FromWorld for OutlinePipeline {
                 },
                 fragment: Some(FragmentState {
                     shader: composite_shader.clone(),
+                    shader_defs,
+                    entry_point: Some("fragment".into()),
+                    targets,
+                }),
+                primitive: PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: MultisampleState::default(),
+                push_constant_ranges: vec![],
+                zero_initialize_workgroup_memory: false,
+            })
+        };
+        let composite_pipeline_id = queue_composite_pipeline(
+            "jfa_composite_pipeline",
+            TextureFormat::bevy_default(),
+            false,
+        );
+        let composite_pipeline_id_hdr = queue_composite_pipeline(
+            "jfa_composite_pipeline_hdr",
+            ViewTarget::TEXTURE_FORMAT_HDR,
+            false,
+        );
+        let composite_pipeline_id_smoothed = queue_composite_pipeline(
+            "jfa_composite_pipeline_smoothed",
+            TextureFormat::bevy_default(),
+            true,
+        );
+        let composite_pipeline_id_hdr_smoothed = queue_composite_pipeline(
+            "jfa_composite_pipeline_hdr_smoothed",
+            ViewTarget::TEXTURE_FORMAT_HDR,
+            true,
+        );
+
+        // ========== Depth Edge Pipeline ==========
+        let depth_edge_shader =
+            asset_server.load("embedded://bevy_outliner/shaders/depth_edges.wgsl");
+
+        let depth_edge_layout_entries = BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                // Scene texture
+                texture_2d(TextureSampleType::Float { filterable: true }),
+                // Scene sampler
+                sampler_layout(SamplerBindingType::Filtering),
+                // Depth prepass texture
+                texture_depth_2d(),
+                // Normal prepass texture
+                texture_2d(TextureSampleType::Float { filterable: false }),
+                // Settings uniform
+                uniform_buffer::<DepthEdgeShaderSettings>(false),
+            ),
+        );
+
+        let depth_edge_layout = render_device.create_bind_group_layout(
+            Some("depth_edge_bind_group_layout"),
+            &depth_edge_layout_entries,
+        );
+
+        let depth_edge_layout_desc = BindGroupLayoutDescriptor::new(
+            "depth_edge_bind_group_layout",
+            &depth_edge_layout_entries,
+        );
+
+        let depth_edge_pipeline_id =
+            pipeline_cache.queue_render_pipeline(RenderPipelineDescriptor {
+                label: Some("depth_edge_pipeline".into()),
+                layout: vec![depth_edge_layout_desc.clone()],
+                vertex: bevy::render::render_resource::VertexState {
+                    shader: vertex_shader.clone(),
+                    shader_defs: vec![],
+                    entry_point: Some("fullscreen_vertex_shader".into()),
+                    buffers: vec![],
+                },
+                fragment: Some(FragmentState {
+                    shader: depth_edge_shader.clone(),
                     shader_defs: vec![],
                     entry_point: Some("fragment".into()),
                     targets: vec![Some(ColorTargetState {
@@ -708,10 +2632,10 @@ impl FromWorld for OutlinePipeline {
                 zero_initialize_workgroup_memory: false,
             });
 
-        let composite_pipeline_id_hdr =
+        let depth_edge_pipeline_id_hdr =
             pipeline_cache.queue_render_pipeline(RenderPipelineDescriptor {
-                label: Some("jfa_composite_pipeline_hdr".into()),
-                layout: vec![composite_layout_desc],
+                label: Some("depth_edge_pipeline_hdr".into()),
+                layout: vec![depth_edge_layout_desc],
                 vertex: bevy::render::render_resource::VertexState {
                     shader: vertex_shader,
                     shader_defs: vec![],
@@ -719,7 +2643,7 @@ impl FromWorld for OutlinePipeline {
                     buffers: vec![],
                 },
                 fragment: Some(FragmentState {
-                    shader: composite_shader,
+                    shader: depth_edge_shader,
                     shader_defs: vec![],
                     entry_point: Some("fragment".into()),
                     targets: vec![Some(ColorTargetState {
@@ -743,30 +2667,58 @@ impl FromWorld for OutlinePipeline {
             composite_layout,
             composite_pipeline_id,
             composite_pipeline_id_hdr,
+            composite_pipeline_id_smoothed,
+            composite_pipeline_id_hdr_smoothed,
+            depth_edge_layout,
+            depth_edge_pipeline_id,
+            depth_edge_pipeline_id_hdr,
+            hover_pick_layout,
+            hover_pick_pipeline_id,
             sampler,
+            fallback_depth,
         }
     }
 }
 
 /// The outline render node - runs JFA passes and composites the result
 /// Uses cached resources from OutlineRenderResources to avoid per-frame allocations
-#[derive(Default)]
-pub struct OutlineNode;
+/// One node is added per [`OutlineStage`], each only drawing views of its stage
+pub struct OutlineNode {
+    stage: OutlineStage,
+}
 
 impl ViewNode for OutlineNode {
     type ViewQuery = (
         &'static ViewTarget,
+        Option<&'static OutlineStage>,
         Option<&'static ExtractedOutlineData>,
         Option<&'static OutlineRenderResources>,
+        Option<&'static DepthEdgeRenderResources>,
+        Option<&'static ViewPrepassTextures>,
+        Option<&'static ExtractedHoverPick>,
     );
 
     fn run<'w>(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext<'w>,
-        (view_target, outline_data, render_resources): bevy::ecs::query::QueryItem<'w, '_, Self::ViewQuery>,
+        (view_target, stage, outline_data, render_resources, depth_edge_resources, prepass_textures, hover_pick): bevy::ecs::query::QueryItem<'w, '_, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
+        if stage.copied().unwrap_or_default() != self.stage {
+            return Ok(());
+        }
+
+        if let Some(depth_edge_resources) = depth_edge_resources {
+            return run_depth_edge_pass(
+                render_context,
+                view_target,
+                depth_edge_resources,
+                prepass_textures,
+                world,
+            );
+        }
+
         let Some(outline_data) = outline_data else {
             return Ok(());
         };
@@ -778,6 +2730,7 @@ impl ViewNode for OutlineNode {
         let outline_pipeline = world.resource::<OutlinePipeline>();
         let pipeline_cache = world.resource::<PipelineCache>();
         let gpu_images = world.resource::<RenderAssets<GpuImage>>();
+        let fallback_image = world.resource::<FallbackImage>();
 
         // Get silhouette texture for composite pass
         let Some(silhouette_gpu) = gpu_images.get(&outline_data.silhouette_texture) else {
@@ -786,6 +2739,25 @@ impl ViewNode for OutlineNode {
         let Some(jfa_ping_gpu) = gpu_images.get(&outline_data.jfa_ping_texture) else {
             return Ok(());
         };
+        // Last and this frame's outline strength, while temporal smoothing
+        // is on. Outlines are drawn unsmoothed until both are uploaded
+        let history = outline_data
+            .history_textures
+            .as_ref()
+            .and_then(|[read, write]| Some((gpu_images.get(read)?, gpu_images.get(write)?)));
+        let Some(object_styles) = world.get_resource::<OutlineObjectStyleBuffer>() else {
+            return Ok(());
+        };
+        // Only drawn into while the user composites the outlines
+        let outline_gpu = match &outline_data.outline_texture {
+            Some(outline_texture) => {
+                let Some(outline_gpu) = gpu_images.get(outline_texture) else {
+                    return Ok(());
+                };
+                Some(outline_gpu)
+            }
+            None => None,
+        };
 
         // Get compute pipelines
         let Some(init_pipeline) = pipeline_cache.get_compute_pipeline(outline_pipeline.init_pipeline_id) else {
@@ -795,10 +2767,13 @@ impl ViewNode for OutlineNode {
             return Ok(());
         };
 
-        let composite_pipeline_id = if view_target.is_hdr() {
-            outline_pipeline.composite_pipeline_id_hdr
-        } else {
-            outline_pipeline.composite_pipeline_id
+        // The outline texture shares the HDR view format
+        let hdr = view_target.is_hdr() || outline_gpu.is_some();
+        let composite_pipeline_id = match (hdr, history.is_some()) {
+            (false, false) => outline_pipeline.composite_pipeline_id,
+            (true, false) => outline_pipeline.composite_pipeline_id_hdr,
+            (false, true) => outline_pipeline.composite_pipeline_id_smoothed,
+            (true, true) => outline_pipeline.composite_pipeline_id_hdr_smoothed,
         };
         let Some(composite_pipeline) = pipeline_cache.get_render_pipeline(composite_pipeline_id) else {
             return Ok(());
@@ -809,11 +2784,61 @@ impl ViewNode for OutlineNode {
         // Calculate workgroup count (8x8 workgroups)
         let tex_width = jfa_ping_gpu.texture.width();
         let tex_height = jfa_ping_gpu.texture.height();
-        let workgroups_x = (tex_width + 7) / 8;
-        let workgroups_y = (tex_height + 7) / 8;
+        let workgroups_x = tex_width.div_ceil(8);
+        let workgroups_y = tex_height.div_ceil(8);
+
+        // Hover Pick Pass: Read the object ID under the cursor for readback
+        if let Some(hover_pick) = hover_pick {
+            let pick_buffer = world
+                .resource::<RenderAssets<GpuShaderStorageBuffer>>()
+                .get(&hover_pick.buffer);
+            let pick_pipeline =
+                pipeline_cache.get_compute_pipeline(outline_pipeline.hover_pick_pipeline_id);
+            if let (Some(pick_buffer), Some(pick_pipeline)) = (pick_buffer, pick_pipeline) {
+                let pick_bind_group = render_context.render_device().create_bind_group(
+                    "hover_pick_bind_group",
+                    &outline_pipeline.hover_pick_layout,
+                    &BindGroupEntries::sequential((
+                        &silhouette_gpu.texture_view,
+                        pick_buffer.buffer.as_entire_binding(),
+                    )),
+                );
+
+                let mut compute_pass =
+                    render_context
+                        .command_encoder()
+                        .begin_compute_pass(&ComputePassDescriptor {
+                            label: Some("hover_pick_compute_pass"),
+                            timestamp_writes: None,
+                        });
+
+                compute_pass.set_pipeline(pick_pipeline);
+                compute_pass.set_bind_group(0, &pick_bind_group, &[]);
+                compute_pass.dispatch_workgroups(1, 1, 1);
+            }
+        }
+
+        // Only hoverables are in view, nothing to draw
+        if !outline_data.draws_outlines {
+            return Ok(());
+        }
+
+        // Thin outlines find their seeds in the composite pass instead
+        let jfa = outline_data.settings.dilate < 0.5;
 
         // Init Compute Pass: Convert silhouette to seed coordinates
-        {
+        if jfa {
+            // Created per frame, as the style buffer is reallocated when it grows
+            let init_bind_group = render_context.render_device().create_bind_group(
+                "jfa_init_compute_bind_group",
+                &outline_pipeline.init_layout,
+                &BindGroupEntries::sequential((
+                    &silhouette_gpu.texture_view,
+                    &render_resources.ping_view,
+                    object_styles.buffer.as_entire_binding(),
+                )),
+            );
+
             let mut compute_pass =
                 render_context
                     .command_encoder()
@@ -823,12 +2848,12 @@ impl ViewNode for OutlineNode {
                     });
 
             compute_pass.set_pipeline(init_pipeline);
-            compute_pass.set_bind_group(0, &render_resources.init_bind_group, &[]);
+            compute_pass.set_bind_group(0, &init_bind_group, &[]);
             compute_pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
         }
 
         // JFA Step Compute Passes: Propagate seeds with decreasing step sizes
-        for step_bind_group in &render_resources.step_bind_groups {
+        for step_bind_group in render_resources.step_bind_groups.iter().filter(|_| jfa) {
             let mut compute_pass =
                 render_context
                     .command_encoder()
@@ -850,47 +2875,183 @@ impl ViewNode for OutlineNode {
             &render_resources.pong_view
         };
 
-        // Composite Pass: Blend outline over scene using JFA distance field
-        // Note: composite_bind_group must be created each frame because post_process.source changes
-        {
-            let post_process = view_target.post_process_write();
+        // Multisampled depth can't be bound as a plain depth texture
+        let scene_depth = prepass_textures
+            .and_then(|textures| textures.depth.as_ref())
+            .filter(|depth| depth.texture.texture.sample_count() == 1)
+            .map_or(&outline_pipeline.fallback_depth, |depth| {
+                &depth.texture.default_view
+            });
 
-            let composite_bind_group = render_context.render_device().create_bind_group(
+        // Composite Pass: Blend outline over scene using JFA distance field
+        let composite_bind_group = |render_device: &RenderDevice, source: &TextureView| {
+            render_device.create_bind_group(
                 "jfa_composite_bind_group",
                 &outline_pipeline.composite_layout,
                 &BindGroupEntries::sequential((
-                    post_process.source,
+                    source,
                     &outline_pipeline.sampler,
                     jfa_result_view,
                     &outline_pipeline.sampler,
                     &silhouette_gpu.texture_view,
                     &outline_pipeline.sampler,
                     render_resources.settings_buffer.as_entire_binding(),
+                    history.map_or(&fallback_image.d2.texture_view, |(read, _)| {
+                        &read.texture_view
+                    }),
+                    object_styles.buffer.as_entire_binding(),
+                    scene_depth,
                 )),
-            );
-
-            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
-                label: Some("jfa_composite_pass"),
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: post_process.destination,
+            )
+        };
+        let history_attachments: Vec<_> = history
+            .iter()
+            .map(|(_, write)| {
+                Some(RenderPassColorAttachment {
+                    view: &write.texture_view,
                     resolve_target: None,
                     ops: Operations::default(),
                     depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
+                })
+            })
+            .collect();
+
+        let Some(outline_gpu) = outline_gpu else {
+            run_view_target_pass(
+                render_context,
+                view_target,
+                "jfa_composite_pass",
+                composite_pipeline,
+                composite_bind_group,
+                &history_attachments,
+            );
+            return Ok(());
+        };
 
-            render_pass.set_render_pipeline(composite_pipeline);
-            render_pass.set_bind_group(0, &composite_bind_group, &[]);
-            render_pass.draw(0..3, 0..1);
-        }
+        // Draw the outline alone for custom compositing, only reading the
+        // scene for auto contrast
+        let bind_group = composite_bind_group(
+            render_context.render_device(),
+            view_target.main_texture_view(),
+        );
+        let mut color_attachments = vec![Some(RenderPassColorAttachment {
+            view: &outline_gpu.texture_view,
+            resolve_target: None,
+            ops: Operations::default(),
+            depth_slice: None,
+        })];
+        color_attachments.extend(history_attachments);
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("jfa_outline_texture_pass"),
+            color_attachments: &color_attachments,
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_render_pipeline(composite_pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
 
         Ok(())
     }
 }
 
+/// Draws a fullscreen pass over the view target
+///
+/// Every pass writing the view target goes through here, calling
+/// `post_process_write` once per pass: each pass reads the texture the
+/// previous one wrote and draws into the other, so passes chain without
+/// reading and writing the same texture. `bind_group` builds the pass's
+/// bind group around the texture to read, and `extra_targets` are color
+/// attachments drawn after the view target.
+fn run_view_target_pass(
+    render_context: &mut RenderContext,
+    view_target: &ViewTarget,
+    label: &'static str,
+    pipeline: &RenderPipeline,
+    bind_group: impl FnOnce(&RenderDevice, &TextureView) -> BindGroup,
+    extra_targets: &[Option<RenderPassColorAttachment>],
+) {
+    // Bind groups must be created per pass, as the source flips each write
+    let post_process = view_target.post_process_write();
+    let bind_group = bind_group(render_context.render_device(), post_process.source);
+
+    let mut color_attachments = vec![Some(RenderPassColorAttachment {
+        view: post_process.destination,
+        resolve_target: None,
+        ops: Operations::default(),
+        depth_slice: None,
+    })];
+    color_attachments.extend_from_slice(extra_targets);
+
+    let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &color_attachments,
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    render_pass.set_render_pipeline(pipeline);
+    render_pass.set_bind_group(0, &bind_group, &[]);
+    render_pass.draw(0..3, 0..1);
+}
+
+/// Detects depth and normal discontinuities from the prepass textures and
+/// composites them over the scene
+fn run_depth_edge_pass(
+    render_context: &mut RenderContext,
+    view_target: &ViewTarget,
+    depth_edge_resources: &DepthEdgeRenderResources,
+    prepass_textures: Option<&ViewPrepassTextures>,
+    world: &World,
+) -> Result<(), NodeRunError> {
+    // Prepass textures appear once the inserted prepass components are extracted
+    let Some(prepass_textures) = prepass_textures else {
+        return Ok(());
+    };
+    let (Some(depth_view), Some(normal_view)) =
+        (prepass_textures.depth_view(), prepass_textures.normal_view())
+    else {
+        return Ok(());
+    };
+
+    let outline_pipeline = world.resource::<OutlinePipeline>();
+    let pipeline_cache = world.resource::<PipelineCache>();
+
+    let pipeline_id = if view_target.is_hdr() {
+        outline_pipeline.depth_edge_pipeline_id_hdr
+    } else {
+        outline_pipeline.depth_edge_pipeline_id
+    };
+    let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id) else {
+        return Ok(());
+    };
+
+    run_view_target_pass(
+        render_context,
+        view_target,
+        "depth_edge_pass",
+        pipeline,
+        |render_device, source| {
+            render_device.create_bind_group(
+                "depth_edge_bind_group",
+                &outline_pipeline.depth_edge_layout,
+                &BindGroupEntries::sequential((
+                    source,
+                    &outline_pipeline.sampler,
+                    depth_view,
+                    normal_view,
+                    depth_edge_resources.settings_buffer.as_entire_binding(),
+                )),
+            )
+        },
+        &[],
+    );
+
+    Ok(())
+}
+
 /// Plugin that sets up the outline render node
 pub struct OutlineRenderPlugin;
 
@@ -900,10 +3061,26 @@ impl Plugin for OutlineRenderPlugin {
             return;
         };
 
+        for stage in OutlineStage::ALL {
+            let node = ViewNodeRunner::new(OutlineNode { stage }, render_app.world_mut());
+            let mut render_graph = render_app.world_mut().resource_mut::<RenderGraph>();
+            if let Some(graph) = render_graph.get_sub_graph_mut(Core3d) {
+                graph.add_node(OutlineStageNodeLabel(stage), node);
+            }
+        }
+
         render_app
-            .add_systems(ExtractSchedule, extract_outline_data)
-            .add_systems(Render, prepare_outline_resources)
-            .add_render_graph_node::<ViewNodeRunner<OutlineNode>>(Core3d, OutlineNodeLabel)
+            .init_resource::<ExtractedOutlineObjectStyles>()
+            .add_systems(ExtractSchedule, (extract_outline_data, extract_hover_picks))
+            .add_systems(
+                Render,
+                (
+                    prepare_outline_resources,
+                    prepare_hover_picks,
+                    prepare_outline_object_styles,
+                    prepare_depth_edge_resources,
+                ),
+            )
             .add_render_graph_edges(
                 Core3d,
                 (
@@ -911,6 +3088,22 @@ impl Plugin for OutlineRenderPlugin {
                     OutlineNodeLabel,
                     Node3d::EndMainPassPostProcessing,
                 ),
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::StartMainPassPostProcessing,
+                    OutlineStageNodeLabel(OutlineStage::BeforeTonemapping),
+                    Node3d::Tonemapping,
+                ),
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::MainTransmissivePass,
+                    OutlineStageNodeLabel(OutlineStage::BeforeTransparent),
+                    Node3d::MainTransparentPass,
+                ),
             );
     }
 
@@ -922,3 +3115,425 @@ impl Plugin for OutlineRenderPlugin {
         render_app.init_resource::<OutlinePipeline>();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Headless app with the resources the outline systems read
+    fn outline_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Assets<Image>>()
+            .init_resource::<Assets<SilhouetteMaterial>>()
+            .init_resource::<OutlineObjectIds>()
+            .init_resource::<OutlineRenderLayer>()
+            .init_resource::<OutlineCameraLayer>()
+            // Added by the visibility plugin in a full app
+            .register_required_components::<Mesh3d, Visibility>();
+        app
+    }
+
+    #[test]
+    fn outlined_mesh_gets_silhouette() {
+        let mut app = outline_app();
+        app.insert_resource(SilhouetteWhiteMaterial(Handle::default()))
+            .add_systems(Update, sync_outline_meshes);
+        // Silhouettes are only spawned while a camera renders them
+        app.world_mut().spawn(OutlineSettings::default());
+        let source = app
+            .world_mut()
+            .spawn((
+                Mesh3d(Handle::default()),
+                MeshOutline::default(),
+                GlobalTransform::default(),
+            ))
+            .id();
+
+        app.update();
+
+        let world = app.world_mut();
+        let silhouettes: Vec<Entity> = world
+            .query::<&SilhouetteMesh>()
+            .iter(world)
+            .map(|silhouette| silhouette.source)
+            .collect();
+        assert_eq!(silhouettes, [source]);
+        assert!(world.get::<HasSilhouetteMesh>(source).is_some());
+    }
+
+    #[test]
+    fn silhouette_cameras_render_before_every_camera() {
+        let mut app = outline_app();
+        app.add_systems(
+            Update,
+            (setup_outline_camera, sync_silhouette_cameras).chain(),
+        );
+        let main = app
+            .world_mut()
+            .spawn((
+                Camera3d::default(),
+                Camera {
+                    order: 2,
+                    ..default()
+                },
+                OutlineSettings::default(),
+            ))
+            .id();
+        // A portal camera right before the main view, outlined too
+        let portal = app
+            .world_mut()
+            .spawn((
+                Camera3d::default(),
+                Camera {
+                    order: 1,
+                    ..default()
+                },
+                OutlineSettings::default(),
+            ))
+            .id();
+
+        app.update();
+
+        let silhouette_order = |app: &App, camera: Entity| {
+            let link = app.world().get::<OutlineCameraLink>(camera).unwrap();
+            app.world()
+                .get::<Camera>(link.silhouette_camera)
+                .unwrap()
+                .order
+        };
+        assert_eq!(silhouette_order(&app, main), 0);
+        assert_eq!(silhouette_order(&app, portal), 0);
+
+        // A camera spawned below them moves every silhouette camera further down
+        app.world_mut().spawn(Camera {
+            order: -3,
+            ..default()
+        });
+        app.update();
+        assert_eq!(silhouette_order(&app, main), -4);
+        assert_eq!(silhouette_order(&app, portal), -4);
+    }
+
+    #[test]
+    fn fragment_shader_is_per_object() {
+        let mut app = outline_app();
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<StandardMaterial>()
+            .init_resource::<OutlineSelection>()
+            .insert_resource(SilhouetteWhiteMaterial(Handle::default()))
+            .add_systems(Update, (sync_outline_meshes, sync_silhouette_masks).chain());
+        app.world_mut().spawn(OutlineSettings::default());
+        let mut spawn = || {
+            app.world_mut()
+                .spawn((
+                    Mesh3d(Handle::default()),
+                    MeshOutline::default(),
+                    GlobalTransform::default(),
+                ))
+                .id()
+        };
+        let custom = spawn();
+        let plain = spawn();
+        app.world_mut()
+            .entity_mut(custom)
+            .insert(OutlineFragmentShader(Handle::default()));
+
+        app.update();
+
+        let silhouette_material = |app: &App, source: Entity| {
+            let silhouette = app
+                .world()
+                .get::<HasSilhouetteMesh>(source)
+                .unwrap()
+                .silhouette;
+            let handle = &app
+                .world()
+                .get::<MeshMaterial3d<SilhouetteMaterial>>(silhouette)
+                .unwrap()
+                .0;
+            app.world()
+                .resource::<Assets<SilhouetteMaterial>>()
+                .get(handle)
+                .cloned()
+        };
+        let material = silhouette_material(&app, custom).unwrap();
+        assert_eq!(material.fragment_shader, Some(Handle::default()));
+        assert!(silhouette_material(&app, plain).is_none());
+
+        // Removing the shader returns the silhouette to the white material
+        app.world_mut()
+            .entity_mut(custom)
+            .remove::<OutlineFragmentShader>();
+        app.update();
+        assert!(silhouette_material(&app, custom).is_none());
+    }
+
+    #[test]
+    fn masked_instances_share_a_silhouette_material() {
+        let mut app = outline_app();
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<StandardMaterial>()
+            .init_resource::<OutlineSelection>()
+            .insert_resource(SilhouetteWhiteMaterial(Handle::default()))
+            .add_systems(Update, (sync_outline_meshes, sync_silhouette_masks).chain());
+        app.world_mut().spawn(OutlineSettings::default());
+        let mut materials = app.world_mut().resource_mut::<Assets<StandardMaterial>>();
+        // Alpha-cutout, so its silhouettes can't use the white material
+        let masked = materials.add(StandardMaterial {
+            base_color_texture: Some(Handle::default()),
+            alpha_mode: AlphaMode::Mask(0.5),
+            ..default()
+        });
+        let plain = materials.add(StandardMaterial::default());
+        let mut spawn = |material: &Handle<StandardMaterial>| {
+            app.world_mut()
+                .spawn((
+                    Mesh3d(Handle::default()),
+                    MeshMaterial3d(material.clone()),
+                    MeshOutline::default(),
+                    GlobalTransform::default(),
+                ))
+                .id()
+        };
+        let instances = [spawn(&masked), spawn(&masked), spawn(&masked)];
+        let plain_source = spawn(&plain);
+
+        app.update();
+
+        let silhouette_material = |app: &App, source: Entity| {
+            let silhouette = app
+                .world()
+                .get::<HasSilhouetteMesh>(source)
+                .unwrap()
+                .silhouette;
+            app.world()
+                .get::<MeshMaterial3d<SilhouetteMaterial>>(silhouette)
+                .unwrap()
+                .0
+                .clone()
+        };
+        let shared = silhouette_material(&app, instances[0]);
+        assert_ne!(shared, Handle::default());
+        for instance in instances {
+            assert_eq!(silhouette_material(&app, instance), shared);
+        }
+        assert_eq!(silhouette_material(&app, plain_source), Handle::default());
+        assert_eq!(
+            app.world().resource::<Assets<SilhouetteMaterial>>().len(),
+            1
+        );
+
+        // Going opaque again returns every instance to the white material
+        app.world_mut()
+            .resource_mut::<Assets<StandardMaterial>>()
+            .get_mut(&masked)
+            .unwrap()
+            .alpha_mode = AlphaMode::Opaque;
+        app.update();
+        app.update();
+        for instance in instances {
+            assert_eq!(silhouette_material(&app, instance), Handle::default());
+        }
+    }
+
+    #[test]
+    fn multisampled_camera_with_writeback() {
+        // Only the stage before the transparent pass is moved off MSAA cameras
+        let msaa = Msaa::Sample4;
+        for (stage, expected) in [
+            (
+                OutlineStage::BeforeTransparent,
+                OutlineStage::BeforeTonemapping,
+            ),
+            (
+                OutlineStage::BeforeTonemapping,
+                OutlineStage::BeforeTonemapping,
+            ),
+            (
+                OutlineStage::AfterTonemapping,
+                OutlineStage::AfterTonemapping,
+            ),
+        ] {
+            assert_eq!(effective_stage(stage, msaa), expected);
+        }
+
+        let mut app = outline_app();
+        app.add_systems(Update, setup_outline_camera);
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera3d::default(),
+                Camera {
+                    order: 1,
+                    msaa_writeback: MsaaWriteback::Always,
+                    ..default()
+                },
+                msaa,
+                OutlineSettings::default(),
+            ))
+            .id();
+
+        app.update();
+
+        // The main camera keeps its MSAA and writeback, while its silhouette
+        // camera renders unresolved data just before it
+        let world = app.world();
+        assert_eq!(world.get::<Msaa>(camera), Some(&msaa));
+        assert_eq!(
+            world.get::<Camera>(camera).unwrap().msaa_writeback,
+            MsaaWriteback::Always
+        );
+        let link = world
+            .get::<OutlineCameraLink>(camera)
+            .expect("outline camera was not set up");
+        let silhouette_camera = world.entity(link.silhouette_camera);
+        assert!(silhouette_camera.contains::<SilhouetteCamera>());
+        assert_eq!(silhouette_camera.get::<Msaa>(), Some(&Msaa::Off));
+        assert_eq!(silhouette_camera.get::<Camera>().unwrap().order, 0);
+    }
+
+    #[test]
+    fn silhouette_camera_ignores_exposure_and_tonemapping() {
+        let mut app = outline_app();
+        app.add_systems(
+            Update,
+            (setup_outline_camera, sync_silhouette_cameras).chain(),
+        );
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera3d::default(),
+                Tonemapping::TonyMcMapface,
+                DebandDither::Enabled,
+                bevy::camera::Exposure::SUNLIGHT,
+                OutlineSettings::default(),
+            ))
+            .id();
+
+        app.update();
+        app.update();
+
+        let world = app.world();
+        let link = world.get::<OutlineCameraLink>(camera).unwrap();
+        let silhouette_camera = world.entity(link.silhouette_camera);
+        assert!(silhouette_camera.contains::<Hdr>());
+        assert_eq!(
+            silhouette_camera.get::<Tonemapping>(),
+            Some(&Tonemapping::None)
+        );
+        assert_eq!(
+            silhouette_camera.get::<DebandDither>(),
+            Some(&DebandDither::Disabled)
+        );
+        // The main camera's exposure isn't copied over
+        assert!(!silhouette_camera.contains::<bevy::camera::Exposure>());
+    }
+
+    #[test]
+    fn stereo_eyes_get_their_own_outlines() {
+        use bevy::camera::{ManualTextureViewHandle, RenderTargetInfo};
+
+        let mut app = outline_app();
+        app.add_systems(
+            Update,
+            (
+                setup_outline_camera,
+                sync_silhouette_cameras,
+                resize_silhouette_textures,
+            )
+                .chain(),
+        );
+        let eye_size = UVec2::new(1832, 1920);
+        let mut spawn_eye = |view: u32, offset: f32, fov: f32| {
+            let mut camera = Camera::default();
+            // Set by Bevy's camera system from `ManualTextureViews` in a full app
+            camera.computed.target_info = Some(RenderTargetInfo {
+                physical_size: eye_size,
+                scale_factor: 1.0,
+            });
+            app.world_mut()
+                .spawn((
+                    Camera3d::default(),
+                    camera,
+                    RenderTarget::TextureView(ManualTextureViewHandle(view)),
+                    Projection::Perspective(PerspectiveProjection { fov, ..default() }),
+                    GlobalTransform::from_translation(Vec3::X * offset),
+                    OutlineSettings::default(),
+                ))
+                .id()
+        };
+        let eyes = [spawn_eye(0, -0.032, 1.6), spawn_eye(1, 0.032, 1.7)];
+
+        app.update();
+
+        let world = app.world();
+        let images = world.resource::<Assets<Image>>();
+        let links = eyes.map(|eye| world.get::<OutlineCameraLink>(eye).unwrap());
+        assert_ne!(links[0].silhouette_camera, links[1].silhouette_camera);
+        assert_ne!(links[0].silhouette_texture, links[1].silhouette_texture);
+        for (eye, link) in eyes.into_iter().zip(links) {
+            for texture in [&link.silhouette_texture, &link.jfa_ping_texture] {
+                assert_eq!(images.get(texture).unwrap().size(), eye_size);
+            }
+            let silhouette_camera = world.entity(link.silhouette_camera);
+            assert_eq!(
+                silhouette_camera.get::<GlobalTransform>(),
+                world.get::<GlobalTransform>(eye)
+            );
+            let (
+                Some(Projection::Perspective(eye_projection)),
+                Some(Projection::Perspective(projection)),
+            ) = (
+                world.get::<Projection>(eye),
+                silhouette_camera.get::<Projection>(),
+            )
+            else {
+                panic!("eye and silhouette camera should have perspective projections");
+            };
+            assert_eq!(projection.fov, eye_projection.fov);
+        }
+    }
+
+    #[test]
+    fn larger_image_target_resizes_outline_textures() {
+        let mut app = outline_app();
+        app.add_systems(
+            Update,
+            (setup_outline_camera, resize_silhouette_textures).chain(),
+        );
+        let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+        let image = |size: UVec2| {
+            Image::new_target_texture(size.x, size.y, TextureFormat::bevy_default(), None)
+        };
+        let view = images.add(image(UVec2::new(320, 180)));
+        // A screenshot at 4x the view's resolution
+        let screenshot = images.add(image(UVec2::new(1280, 720)));
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera3d::default(),
+                RenderTarget::Image(view.into()),
+                OutlineSettings::default(),
+            ))
+            .id();
+        app.update();
+
+        app.world_mut()
+            .entity_mut(camera)
+            .insert(RenderTarget::Image(screenshot.into()));
+        app.update();
+
+        let world = app.world();
+        let link = world.get::<OutlineCameraLink>(camera).unwrap();
+        let images = world.resource::<Assets<Image>>();
+        for texture in [
+            &link.silhouette_texture,
+            &link.jfa_ping_texture,
+            &link.jfa_pong_texture,
+            &link.outline_texture,
+        ] {
+            assert_eq!(images.get(texture).unwrap().size(), UVec2::new(1280, 720));
+        }
+    }
+}
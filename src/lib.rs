@@ -38,28 +38,107 @@
 //! }
 //! ```
 
+mod categories;
+#[cfg(feature = "picking")]
+mod click_select;
 mod components;
+mod emissive;
+mod grow_in;
+mod hover;
 mod jfa_material;
+mod light_tint;
+mod motion;
+mod presets;
+mod proximity;
+mod proxy;
+mod selection;
 mod silhouette_material;
+mod value;
 
 pub mod prelude {
-    pub use crate::components::{MeshOutline, OutlineSettings};
-    pub use crate::OutlinePlugin;
+    pub use crate::categories::{OutlineCategories, OutlineCategory};
+    #[cfg(feature = "picking")]
+    pub use crate::click_select::{OutlineClickSelect, OutlineSelected};
+    pub use crate::components::{
+        DepthEdgeSettings, JfaSchedule, MeshOutline, OutlineDash, OutlineFragmentShader,
+        OutlineHatch, OutlineMode, OutlineSettings, OutlineShape, OutlineStage, OutlineTargetSize,
+        OutlineVertexShader, OutlineWidthCurve, SilhouetteMeshOverride, WidthUnit,
+    };
+    pub use crate::emissive::OutlineEmissiveResponse;
+    pub use crate::grow_in::OutlineGrowIn;
+    pub use crate::hover::{OutlineHover, OutlineHoverable};
+    pub use crate::jfa_material::{
+        OutlineAllow, OutlineCameraLayer, OutlineCameraLink, OutlineDeny, OutlineRenderLayer,
+    };
+    pub use crate::light_tint::OutlineLightTint;
+    pub use crate::motion::OutlineMotionEmphasis;
+    pub use crate::presets::OutlineColorPreset;
+    pub use crate::proximity::OutlineProximity;
+    pub use crate::proxy::OutlineProxy;
+    pub use crate::selection::OutlineSelection;
+    pub use crate::value::OutlineValue;
+    pub use crate::{OutlinePlugin, OutlineSystems};
 }
 
+pub use categories::{OutlineCategories, OutlineCategory};
+#[cfg(feature = "picking")]
+pub use click_select::{OutlineClickSelect, OutlineSelected};
 pub use components::*;
+pub use emissive::OutlineEmissiveResponse;
+pub use grow_in::OutlineGrowIn;
+pub use hover::{OutlineHover, OutlineHoverable};
+pub use jfa_material::{
+    OutlineAllow, OutlineCameraLayer, OutlineCameraLink, OutlineDeny, OutlineDistanceField,
+    OutlineNodeLabel, OutlineRenderLayer, OutlineStageNodeLabel, OutlineTexture,
+};
+pub use light_tint::OutlineLightTint;
+pub use motion::OutlineMotionEmphasis;
+pub use presets::OutlineColorPreset;
+pub use proximity::OutlineProximity;
+pub use proxy::OutlineProxy;
+pub use selection::OutlineSelection;
+pub use value::OutlineValue;
 
-use bevy::{asset::embedded_asset, prelude::*};
+use bevy::{
+    asset::embedded_asset,
+    camera::{visibility::VisibilitySystems, CameraUpdateSystems},
+    prelude::*,
+};
 
+use categories::update_outline_categories;
+use emissive::update_outline_emissive;
+use grow_in::update_outline_grow_in;
+use hover::{setup_hover_picks, update_outline_hover};
 use jfa_material::{
-    resize_silhouette_textures, setup_outline_camera, sync_outline_meshes, sync_silhouette_cameras,
-    OutlineRenderPlugin,
+    limit_outline_max_width, resize_silhouette_textures, setup_depth_edge_cameras,
+    setup_outline_camera, sync_outline_meshes, sync_outline_render_layer,
+    sync_silhouette_cameras, sync_silhouette_masks, sync_silhouette_projections,
+    sync_silhouette_shapes, warn_outline_layer_conflicts, OutlineObjectIds, OutlineRenderPlugin,
 };
+use light_tint::update_outline_light_tint;
+use motion::update_outline_motion;
+use proximity::update_outline_proximity;
+use proxy::sync_outline_proxies;
+use selection::sync_selection_meshes;
 use silhouette_material::SilhouetteMaterial;
+use value::update_outline_value;
 
 /// Plugin that enables silhouette-based object outlining.
+///
+/// The plugin is unique: adding it a second time fails with Bevy's
+/// "plugin was already added" error before anything is registered twice.
 pub struct OutlinePlugin;
 
+/// `PostUpdate` systems that spawn and sync silhouettes.
+///
+/// Entities that have a mesh and [`MeshOutline`] by the time this set runs,
+/// including ones spawned by observers or deferred commands earlier in the
+/// frame, are outlined in the same frame. Systems in `PostUpdate` that spawn
+/// outlined entities, or trigger observers that do, should run
+/// `.before(OutlineSystems)` to avoid a frame of delay.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OutlineSystems;
+
 impl Plugin for OutlinePlugin {
     fn build(&self, app: &mut App) {
         // Embed shaders
@@ -67,20 +146,59 @@ impl Plugin for OutlinePlugin {
         embedded_asset!(app, "shaders/jfa_step_compute.wgsl");
         embedded_asset!(app, "shaders/jfa_composite.wgsl");
         embedded_asset!(app, "shaders/silhouette.wgsl");
+        embedded_asset!(app, "shaders/depth_edges.wgsl");
+        embedded_asset!(app, "shaders/hover_pick.wgsl");
 
         app.add_plugins((
             OutlineRenderPlugin,
             MaterialPlugin::<SilhouetteMaterial>::default(),
         ))
+        .init_resource::<OutlineObjectIds>()
+        .init_resource::<OutlineSelection>()
+        .init_resource::<OutlineHover>()
+        .init_resource::<OutlineCategories>()
+        .init_resource::<OutlineRenderLayer>()
+        .init_resource::<OutlineCameraLayer>()
         .add_systems(
             PostUpdate,
             (
+                update_outline_categories,
+                update_outline_proximity,
+                update_outline_value,
+                update_outline_hover,
+                update_outline_grow_in,
+                update_outline_motion,
+                update_outline_emissive,
+                update_outline_light_tint,
+                limit_outline_max_width,
                 setup_outline_camera,
+                setup_hover_picks,
+                setup_depth_edge_cameras,
+                sync_outline_proxies,
                 sync_outline_meshes,
+                sync_selection_meshes,
+                sync_silhouette_shapes,
+                sync_silhouette_masks,
+                sync_outline_render_layer,
                 sync_silhouette_cameras,
                 resize_silhouette_textures,
             )
-                .chain(),
-        );
+                .chain()
+                .in_set(OutlineSystems)
+                // Read this frame's propagated transforms and write silhouette
+                // transforms before frusta and visibility are computed
+                .after(TransformSystems::Propagate)
+                .before(VisibilitySystems::UpdateFrusta),
+        )
+        .add_systems(
+            PostUpdate,
+            sync_silhouette_projections.before(CameraUpdateSystems),
+        )
+        .add_systems(PostUpdate, warn_outline_layer_conflicts);
+
+        #[cfg(feature = "picking")]
+        app.add_observer(click_select::toggle_selected_on_click)
+            .add_observer(click_select::outline_selected)
+            .add_observer(click_select::unoutline_deselected);
     }
 }
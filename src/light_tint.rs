@@ -0,0 +1,160 @@
+//! Light-driven outline color.
+//!
+//! [`OutlineLightTint`] reads the color of a light entity and tints an
+//! entity's [`MeshOutline`] with it, so rim-like outlines follow the scene
+//! lighting as it changes.
+
+use bevy::prelude::*;
+
+use crate::components::MeshOutline;
+
+/// Tints an entity's outline with the color of a light.
+///
+/// The outline color is multiplied by the light's color, blended in by
+/// `strength`. Works with [`DirectionalLight`], [`PointLight`] and
+/// [`SpotLight`] entities; the outline keeps its own color while `light`
+/// has none of them.
+///
+/// Don't combine with [`OutlineMotionEmphasis`](crate::OutlineMotionEmphasis)
+/// or [`OutlineEmissiveResponse`](crate::OutlineEmissiveResponse) on the same
+/// entity, as they all rewrite the outline color.
+#[derive(Component, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct OutlineLightTint {
+    /// Light whose color tints the outline, e.g. the sun.
+    pub light: Entity,
+    /// How much of the light's color is applied, from 0 to 1.
+    pub strength: f32,
+}
+
+impl OutlineLightTint {
+    /// Fully tint the outline with `light`'s color.
+    pub fn new(light: Entity) -> Self {
+        Self {
+            light,
+            strength: 1.0,
+        }
+    }
+}
+
+/// Per-entity state of [`OutlineLightTint`]
+#[derive(Component)]
+pub struct OutlineLightTintState {
+    /// Outline color set by the user, before tinting
+    base_color: LinearRgba,
+    /// Color last written to the outline, to notice user changes
+    written_color: LinearRgba,
+}
+
+/// System to tint [`MeshOutline`] color with the referenced light's color
+pub fn update_outline_light_tint(
+    mut commands: Commands,
+    lights: Query<(
+        Option<&DirectionalLight>,
+        Option<&PointLight>,
+        Option<&SpotLight>,
+    )>,
+    mut entities: Query<(
+        Entity,
+        &OutlineLightTint,
+        &mut MeshOutline,
+        Option<&mut OutlineLightTintState>,
+    )>,
+    mut removed: RemovedComponents<OutlineLightTint>,
+    mut states: Query<(&OutlineLightTintState, &mut MeshOutline), Without<OutlineLightTint>>,
+) {
+    for (entity, tint, mut outline, state) in entities.iter_mut() {
+        let Some(mut state) = state else {
+            commands.entity(entity).insert(OutlineLightTintState {
+                base_color: outline.color,
+                written_color: outline.color,
+            });
+            continue;
+        };
+
+        // The user changed the color since we last wrote it
+        if outline.color != state.written_color {
+            state.base_color = outline.color;
+        }
+
+        let light_color = match lights.get(tint.light) {
+            Ok((Some(light), _, _)) => Some(light.color),
+            Ok((_, Some(light), _)) => Some(light.color),
+            Ok((_, _, Some(light))) => Some(light.color),
+            _ => None,
+        };
+        let base = state.base_color;
+        let color = match light_color {
+            Some(light_color) => {
+                let light = LinearRgba::from(light_color);
+                let t = tint.strength.clamp(0.0, 1.0);
+                LinearRgba::new(
+                    base.red * (1.0 - t + t * light.red),
+                    base.green * (1.0 - t + t * light.green),
+                    base.blue * (1.0 - t + t * light.blue),
+                    base.alpha,
+                )
+            }
+            None => base,
+        };
+
+        // Only write when it changed, so change detection stays quiet
+        if outline.color != color {
+            outline.color = color;
+        }
+        state.written_color = color;
+    }
+
+    // Restore the user's color when the tint is removed
+    for entity in removed.read() {
+        if let Ok((state, mut outline)) = states.get_mut(entity) {
+            if outline.color == state.written_color {
+                outline.color = state.base_color;
+            }
+            commands.entity(entity).remove::<OutlineLightTintState>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outline_takes_the_light_color() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, update_outline_light_tint);
+        let light = app
+            .world_mut()
+            .spawn(PointLight {
+                color: LinearRgba::rgb(1.0, 0.5, 0.0).into(),
+                ..default()
+            })
+            .id();
+        let entity = app
+            .world_mut()
+            .spawn((
+                OutlineLightTint::new(light),
+                MeshOutline::with_color(LinearRgba::WHITE),
+            ))
+            .id();
+        let color = |app: &App| app.world().get::<MeshOutline>(entity).unwrap().color;
+
+        app.update();
+        app.update();
+        assert_eq!(color(&app), LinearRgba::rgb(1.0, 0.5, 0.0));
+
+        // Following the light as it changes
+        app.world_mut().get_mut::<PointLight>(light).unwrap().color =
+            LinearRgba::rgb(0.0, 0.0, 1.0).into();
+        app.update();
+        assert_eq!(color(&app), LinearRgba::rgb(0.0, 0.0, 1.0));
+
+        app.world_mut()
+            .entity_mut(entity)
+            .remove::<OutlineLightTint>();
+        app.update();
+        assert_eq!(color(&app), LinearRgba::WHITE);
+    }
+}
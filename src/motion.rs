@@ -0,0 +1,157 @@
+//! Motion-driven outline emphasis.
+//!
+//! [`OutlineMotionEmphasis`] tracks how fast an entity moves and scales the
+//! opacity of its [`MeshOutline`], so moving objects stand out and resting
+//! ones fade back.
+
+use bevy::prelude::*;
+
+use crate::components::MeshOutline;
+
+/// Scales an entity's outline opacity with its speed.
+///
+/// The outline is drawn at [`rest_intensity`](Self::rest_intensity) while
+/// the entity is still and ramps up to its full [`MeshOutline::color`] as
+/// its speed approaches [`full_speed`](Self::full_speed).
+#[derive(Component, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct OutlineMotionEmphasis {
+    /// Speed in world units per second at which the outline is fully opaque.
+    pub full_speed: f32,
+    /// Fraction of the outline opacity kept while the entity is still.
+    pub rest_intensity: f32,
+    /// How quickly the tracked speed follows the actual speed, per second.
+    /// Higher values react faster but flicker with uneven frame times.
+    pub response: f32,
+}
+
+impl Default for OutlineMotionEmphasis {
+    fn default() -> Self {
+        Self {
+            full_speed: 5.0,
+            rest_intensity: 0.2,
+            response: 10.0,
+        }
+    }
+}
+
+/// Per-entity state of [`OutlineMotionEmphasis`]
+#[derive(Component)]
+pub struct OutlineMotionState {
+    /// Outline color set by the user, before emphasis
+    base_color: LinearRgba,
+    /// Color last written to the outline, to notice user changes
+    written_color: LinearRgba,
+    last_translation: Vec3,
+    speed: f32,
+}
+
+/// System to track entity speed and scale [`MeshOutline`] opacity with it
+pub fn update_outline_motion(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut entities: Query<(
+        Entity,
+        &OutlineMotionEmphasis,
+        &GlobalTransform,
+        &mut MeshOutline,
+        Option<&mut OutlineMotionState>,
+    )>,
+    mut removed: RemovedComponents<OutlineMotionEmphasis>,
+    mut states: Query<(&OutlineMotionState, &mut MeshOutline), Without<OutlineMotionEmphasis>>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, emphasis, global_transform, mut outline, state) in entities.iter_mut() {
+        let translation = global_transform.translation();
+        let Some(mut state) = state else {
+            commands.entity(entity).insert(OutlineMotionState {
+                base_color: outline.color,
+                written_color: outline.color,
+                last_translation: translation,
+                speed: 0.0,
+            });
+            continue;
+        };
+
+        // The user changed the color since we last wrote it
+        if outline.color != state.written_color {
+            state.base_color = outline.color;
+        }
+
+        if delta > 0.0 {
+            let speed = translation.distance(state.last_translation) / delta;
+            let blend = 1.0 - (-emphasis.response * delta).exp();
+            state.speed += (speed - state.speed) * blend;
+        }
+        state.last_translation = translation;
+
+        let motion = (state.speed / emphasis.full_speed.max(f32::EPSILON)).clamp(0.0, 1.0);
+        let intensity = emphasis.rest_intensity.clamp(0.0, 1.0).lerp(1.0, motion);
+        let color = state.base_color.with_alpha(state.base_color.alpha * intensity);
+
+        // Only write when it changed, so change detection stays quiet at rest
+        if outline.color != color {
+            outline.color = color;
+        }
+        state.written_color = color;
+    }
+
+    // Restore the user's color when emphasis is removed
+    for entity in removed.read() {
+        if let Ok((state, mut outline)) = states.get_mut(entity) {
+            if outline.color == state.written_color {
+                outline.color = state.base_color;
+            }
+            commands.entity(entity).remove::<OutlineMotionState>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn moving_entities_get_opaque_outlines() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .add_systems(Update, update_outline_motion);
+        let entity = app
+            .world_mut()
+            .spawn((
+                OutlineMotionEmphasis::default(),
+                MeshOutline::default(),
+                GlobalTransform::default(),
+            ))
+            .id();
+        let alpha = |app: &App| app.world().get::<MeshOutline>(entity).unwrap().color.alpha;
+
+        app.update();
+        app.update();
+        assert_eq!(alpha(&app), 0.2);
+
+        // 10 units per second, twice the full speed
+        for step in 1..=10 {
+            let moved = GlobalTransform::from_translation(Vec3::X * step as f32);
+            app.world_mut().entity_mut(entity).insert(moved);
+            app.update();
+        }
+        assert!(alpha(&app) > 0.99);
+
+        // Removing the emphasis restores the user's color
+        app.world_mut()
+            .entity_mut(entity)
+            .remove::<OutlineMotionEmphasis>();
+        app.update();
+        assert_eq!(alpha(&app), 1.0);
+        assert!(app.world().get::<OutlineMotionState>(entity).is_none());
+    }
+}
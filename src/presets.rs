@@ -0,0 +1,68 @@
+//! Colorblind-safe outline colors.
+//!
+//! The colors come from the Okabe-Ito palette, which stays distinguishable
+//! under deuteranopia, protanopia and tritanopia.
+
+use bevy::prelude::*;
+
+/// Outline colors chosen to remain distinguishable for colorblind players.
+///
+/// ```
+/// # use bevy_outliner::prelude::*;
+/// let outline = MeshOutline::with_color(OutlineColorPreset::Enemy);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum OutlineColorPreset {
+    /// Vermillion.
+    Enemy,
+    /// Blue.
+    Ally,
+    /// Yellow.
+    Neutral,
+    /// Sky blue.
+    Selected,
+    /// Bluish green.
+    Interactable,
+    /// Orange.
+    Highlight,
+    /// Reddish purple.
+    Special,
+}
+
+impl OutlineColorPreset {
+    /// All presets, in declaration order.
+    pub const ALL: [Self; 7] = [
+        Self::Enemy,
+        Self::Ally,
+        Self::Neutral,
+        Self::Selected,
+        Self::Interactable,
+        Self::Highlight,
+        Self::Special,
+    ];
+
+    /// The sRGB color of this preset.
+    pub fn srgba(self) -> Srgba {
+        match self {
+            Self::Enemy => Srgba::rgb_u8(213, 94, 0),
+            Self::Ally => Srgba::rgb_u8(0, 114, 178),
+            Self::Neutral => Srgba::rgb_u8(240, 228, 66),
+            Self::Selected => Srgba::rgb_u8(86, 180, 233),
+            Self::Interactable => Srgba::rgb_u8(0, 158, 115),
+            Self::Highlight => Srgba::rgb_u8(230, 159, 0),
+            Self::Special => Srgba::rgb_u8(204, 121, 167),
+        }
+    }
+}
+
+impl From<OutlineColorPreset> for LinearRgba {
+    fn from(preset: OutlineColorPreset) -> Self {
+        preset.srgba().into()
+    }
+}
+
+impl From<OutlineColorPreset> for Color {
+    fn from(preset: OutlineColorPreset) -> Self {
+        preset.srgba().into()
+    }
+}
@@ -0,0 +1,92 @@
+//! Proximity-triggered outlines.
+//!
+//! [`OutlineProximity`] outlines an entity only while a reference entity,
+//! such as the player or the camera, is within a radius of it.
+
+use bevy::prelude::*;
+
+use crate::components::MeshOutline;
+
+/// Outlines an entity while [`target`](Self::target) is within
+/// [`radius`](Self::radius) of it.
+///
+/// [`outline`](Self::outline) is inserted as the entity's [`MeshOutline`]
+/// when the target comes close and removed when it leaves. Edit this field
+/// rather than the [`MeshOutline`] to change the style.
+#[derive(Component, Clone, Copy)]
+pub struct OutlineProximity {
+    /// Entity whose distance is measured, e.g. the player or camera.
+    pub target: Entity,
+    /// Distance in world units within which the outline is shown.
+    pub radius: f32,
+    /// Outline shown while the target is in range.
+    pub outline: MeshOutline,
+}
+
+impl OutlineProximity {
+    /// Outline with the default style when `target` is within `radius`.
+    pub fn new(target: Entity, radius: f32) -> Self {
+        Self {
+            target,
+            radius,
+            outline: MeshOutline::default(),
+        }
+    }
+}
+
+/// System to add or remove [`MeshOutline`] as targets enter and leave range
+pub fn update_outline_proximity(
+    mut commands: Commands,
+    entities: Query<(Entity, &OutlineProximity, &GlobalTransform, Option<&MeshOutline>)>,
+    targets: Query<&GlobalTransform>,
+) {
+    for (entity, proximity, global_transform, outline) in entities.iter() {
+        let in_range = targets.get(proximity.target).is_ok_and(|target| {
+            target
+                .translation()
+                .distance_squared(global_transform.translation())
+                <= proximity.radius * proximity.radius
+        });
+
+        match outline {
+            None if in_range => {
+                commands.entity(entity).insert(proximity.outline);
+            }
+            Some(_) if !in_range => {
+                commands.entity(entity).remove::<MeshOutline>();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outline_shown_while_target_in_range() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, update_outline_proximity);
+        let target = app.world_mut().spawn(GlobalTransform::default()).id();
+        let entity = app
+            .world_mut()
+            .spawn((
+                OutlineProximity::new(target, 2.0),
+                GlobalTransform::from_translation(Vec3::X),
+            ))
+            .id();
+
+        app.update();
+        assert!(app.world().get::<MeshOutline>(entity).is_some());
+
+        app.world_mut()
+            .entity_mut(target)
+            .insert(GlobalTransform::from_translation(
+                Vec3::X * 5.0 + Vec3::Y * 5.0,
+            ));
+        app.update();
+        assert!(app.world().get::<MeshOutline>(entity).is_none());
+    }
+}
@@ -0,0 +1,127 @@
+//! Outline proxies for entities without a mesh.
+//!
+//! Lights, audio emitters and other component-only entities can't be
+//! outlined directly. An [`OutlineProxy`] gives them an invisible stand-in
+//! mesh whose silhouette is outlined whenever the entity has a
+//! [`MeshOutline`].
+
+use bevy::prelude::*;
+
+use crate::components::MeshOutline;
+
+/// Outlines an entity without a mesh using a proxy mesh, such as an editor
+/// gizmo or icon shape.
+///
+/// The proxy is spawned as an invisible child, so it follows the entity's
+/// transform. Add or remove [`MeshOutline`] on the entity itself to toggle
+/// the outline.
+#[derive(Component, Clone)]
+pub struct OutlineProxy {
+    /// Mesh whose silhouette is outlined.
+    pub mesh: Handle<Mesh>,
+    /// Transform of the proxy relative to the entity.
+    pub transform: Transform,
+}
+
+impl OutlineProxy {
+    /// Create a proxy with the given mesh centered on the entity.
+    pub fn new(mesh: Handle<Mesh>) -> Self {
+        Self {
+            mesh,
+            transform: Transform::IDENTITY,
+        }
+    }
+}
+
+/// Links an [`OutlineProxy`] entity to its spawned proxy mesh
+#[derive(Component)]
+pub struct OutlineProxyMesh(pub Entity);
+
+/// System to spawn proxy meshes and mirror [`MeshOutline`] onto them
+pub fn sync_outline_proxies(
+    mut commands: Commands,
+    new_proxies: Query<
+        (Entity, &OutlineProxy, &GlobalTransform, Option<&MeshOutline>),
+        Without<OutlineProxyMesh>,
+    >,
+    changed_proxies: Query<(&OutlineProxy, &OutlineProxyMesh), Changed<OutlineProxy>>,
+    changed_outlines: Query<(&MeshOutline, &OutlineProxyMesh), Changed<MeshOutline>>,
+    proxy_meshes: Query<&OutlineProxyMesh>,
+    mut removed_outlines: RemovedComponents<MeshOutline>,
+    mut removed_proxies: RemovedComponents<OutlineProxy>,
+) {
+    // Spawn proxy meshes. Without a material they are never drawn by the
+    // main camera, only as a silhouette
+    for (entity, proxy, global_transform, outline) in new_proxies.iter() {
+        // Transforms were already propagated this frame, so place it directly
+        let mut proxy_mesh = commands.spawn((
+            ChildOf(entity),
+            Mesh3d(proxy.mesh.clone()),
+            proxy.transform,
+            global_transform.mul_transform(proxy.transform),
+        ));
+        if let Some(outline) = outline {
+            proxy_mesh.insert(*outline);
+        }
+        let proxy_mesh = proxy_mesh.id();
+        commands.entity(entity).insert(OutlineProxyMesh(proxy_mesh));
+    }
+
+    for (proxy, proxy_mesh) in changed_proxies.iter() {
+        commands
+            .entity(proxy_mesh.0)
+            .insert((Mesh3d(proxy.mesh.clone()), proxy.transform));
+    }
+
+    // Mirror the entity's outline onto its proxy mesh
+    for (outline, proxy_mesh) in changed_outlines.iter() {
+        commands.entity(proxy_mesh.0).insert(*outline);
+    }
+    for entity in removed_outlines.read() {
+        if let Ok(proxy_mesh) = proxy_meshes.get(entity) {
+            commands.entity(proxy_mesh.0).remove::<MeshOutline>();
+        }
+    }
+
+    for entity in removed_proxies.read() {
+        if let Ok(proxy_mesh) = proxy_meshes.get(entity) {
+            commands.entity(proxy_mesh.0).despawn();
+            commands.entity(entity).remove::<OutlineProxyMesh>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proxy_mesh_mirrors_the_outline() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, sync_outline_proxies);
+        let entity = app
+            .world_mut()
+            .spawn((
+                OutlineProxy::new(Handle::default()),
+                GlobalTransform::default(),
+                MeshOutline::default(),
+            ))
+            .id();
+
+        app.update();
+
+        let proxy_mesh = app.world().get::<OutlineProxyMesh>(entity).unwrap().0;
+        assert!(app.world().get::<Mesh3d>(proxy_mesh).is_some());
+        assert!(app.world().get::<MeshOutline>(proxy_mesh).is_some());
+
+        app.world_mut().entity_mut(entity).remove::<MeshOutline>();
+        app.update();
+        assert!(app.world().get::<MeshOutline>(proxy_mesh).is_none());
+
+        // Removing the proxy despawns its mesh
+        app.world_mut().entity_mut(entity).remove::<OutlineProxy>();
+        app.update();
+        assert!(app.world().get_entity(proxy_mesh).is_err());
+    }
+}
@@ -0,0 +1,288 @@
+//! Resource-driven outlining for tools that keep their selection as a set.
+//!
+//! Entities in [`OutlineSelection`] are outlined without adding any component
+//! to them; their silhouette copies are tracked by the resource instead.
+
+use bevy::{
+    camera::{
+        primitives::Aabb,
+        visibility::{NoFrustumCulling, RenderLayers, VisibilityRange},
+    },
+    ecs::entity::{EntityHashMap, EntityHashSet},
+    mesh::skinning::SkinnedMesh,
+    prelude::*,
+};
+
+use crate::components::{MeshOutline, OutlineSettings, OutlineShape, SilhouetteMeshOverride};
+use crate::jfa_material::{
+    apply_silhouette_shape, mirror_silhouette_source, spawn_silhouette, OutlineObjectIds,
+    OutlineRenderLayer, SilhouetteBounds, SilhouetteMesh, SilhouetteWhiteMaterial,
+};
+
+/// Outlines a set of entities without requiring [`MeshOutline`] on each.
+///
+/// Insert entities into [`entities`](Self::entities) to outline them and
+/// remove them to clear the outline. Entities that also have a
+/// [`MeshOutline`] keep using their own component.
+#[derive(Resource, Default)]
+pub struct OutlineSelection {
+    /// Entities to outline.
+    pub entities: EntityHashSet,
+    /// Outline style applied to every selected entity.
+    pub style: MeshOutline,
+    /// How much the style color brightens as more entities are selected,
+    /// scaling it by `1 + count_brightness * ln(count)`. The logarithm keeps
+    /// large selections from blowing out. Set to `0.0` to disable.
+    pub count_brightness: f32,
+    /// Silhouette copy and object ID for each outlined entity
+    silhouettes: EntityHashMap<(Entity, u32)>,
+}
+
+impl OutlineSelection {
+    /// [`style`](Self::style) with [`count_brightness`](Self::count_brightness)
+    /// applied for the current selection size.
+    pub fn effective_style(&self) -> MeshOutline {
+        let count = self.entities.len().max(1) as f32;
+        let scale = 1.0 + self.count_brightness.max(0.0) * count.ln();
+        let color = self.style.color;
+        MeshOutline {
+            color: LinearRgba::new(
+                color.red * scale,
+                color.green * scale,
+                color.blue * scale,
+                color.alpha,
+            ),
+            ..self.style
+        }
+    }
+
+    /// Silhouette copy of a selected entity
+    pub(crate) fn silhouette(&self, entity: Entity) -> Option<Entity> {
+        self.silhouettes.get(&entity).map(|&(silhouette, _)| silhouette)
+    }
+
+    /// Object IDs of the silhouettes of selected entities
+    pub(crate) fn object_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.silhouettes.values().map(|&(_, object_id)| object_id)
+    }
+}
+
+/// System to sync silhouette meshes with the entities in [`OutlineSelection`]
+///
+/// Silhouettes are spawned like those of [`MeshOutline`] entities and only
+/// updated when their source or the selection's shape changes.
+pub fn sync_selection_meshes(
+    mut commands: Commands,
+    mut selection: ResMut<OutlineSelection>,
+    white_material: Option<Res<SilhouetteWhiteMaterial>>,
+    mut object_ids: ResMut<OutlineObjectIds>,
+    outline_layer: Res<OutlineRenderLayer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    sources: Query<
+        (
+            Ref<Mesh3d>,
+            Ref<GlobalTransform>,
+            Option<Ref<VisibilityRange>>,
+            Option<Ref<Aabb>>,
+            Option<Ref<SkinnedMesh>>,
+            Option<&RenderLayers>,
+            Option<Ref<NoFrustumCulling>>,
+            Option<Ref<SilhouetteMeshOverride>>,
+        ),
+        (Without<MeshOutline>, Without<SilhouetteMesh>),
+    >,
+    mut silhouettes: Query<
+        (
+            &mut Transform,
+            &mut GlobalTransform,
+            &Mesh3d,
+            Option<&SilhouetteBounds>,
+        ),
+        With<SilhouetteMesh>,
+    >,
+    mut removed_ranges: RemovedComponents<VisibilityRange>,
+    mut removed_skins: RemovedComponents<SkinnedMesh>,
+    mut removed_no_culling: RemovedComponents<NoFrustumCulling>,
+    mut removed_overrides: RemovedComponents<SilhouetteMeshOverride>,
+    outline_cameras: Query<&OutlineSettings>,
+    // Shape the silhouettes were last built for
+    mut applied_shape: Local<OutlineShape>,
+) {
+    let Some(white_material) = white_material else {
+        return;
+    };
+    let selection = &mut *selection;
+
+    // Remove silhouettes for deselected or despawned entities
+    selection.silhouettes.retain(|entity, (silhouette, object_id)| {
+        if selection.entities.contains(entity) && sources.contains(*entity) {
+            return true;
+        }
+        commands.entity(*silhouette).despawn();
+        object_ids.free(*object_id);
+        false
+    });
+
+    // Silhouettes are only needed while some camera renders them
+    let needs_silhouettes = outline_cameras
+        .iter()
+        .any(OutlineSettings::renders_silhouettes);
+
+    let shape = selection.style.shape;
+    let reshape = *applied_shape != shape;
+    *applied_shape = shape;
+    let removed_mirrored: EntityHashSet = removed_ranges
+        .read()
+        .chain(removed_skins.read())
+        .chain(removed_no_culling.read())
+        .collect();
+    let removed_overrides: EntityHashSet = removed_overrides.read().collect();
+
+    for &entity in selection.entities.iter() {
+        let Ok((
+            mesh,
+            global_transform,
+            range,
+            aabb,
+            skin,
+            render_layers,
+            no_culling,
+            mesh_override,
+        )) = sources.get(entity)
+        else {
+            continue;
+        };
+        let mirrored = (range.as_deref(), skin.as_deref(), no_culling.is_some());
+        // Draw the user's simplified proxy instead of the full mesh if given
+        let mesh_changed = mesh.is_changed()
+            || mesh_override.as_ref().is_some_and(Ref::is_changed)
+            || removed_overrides.contains(&entity);
+        let mesh = Mesh3d(
+            mesh_override
+                .as_deref()
+                .map_or(&mesh.0, |proxy| &proxy.0)
+                .clone(),
+        );
+
+        let Some(&(silhouette, _)) = selection.silhouettes.get(&entity) else {
+            // Add silhouette meshes for newly selected entities
+            let object_id = if needs_silhouettes {
+                object_ids.allocate()
+            } else {
+                None
+            };
+            if let Some(object_id) = object_id {
+                let mut silhouette = spawn_silhouette(
+                    &mut commands,
+                    entity,
+                    object_id,
+                    &white_material,
+                    &global_transform,
+                    outline_layer.silhouette_layers(render_layers),
+                    &mesh.0,
+                    mirrored,
+                );
+                apply_silhouette_shape(
+                    &mut silhouette,
+                    &mut meshes,
+                    shape,
+                    (&mesh, aabb.as_deref()),
+                    (&mesh, None),
+                );
+                let silhouette = silhouette.id();
+                selection.silhouettes.insert(entity, (silhouette, object_id));
+            }
+            continue;
+        };
+
+        let Ok((mut sil_transform, mut sil_global, sil_mesh, bounds)) =
+            silhouettes.get_mut(silhouette)
+        else {
+            continue;
+        };
+
+        // Update silhouette transform when the source moved
+        if global_transform.is_changed() {
+            *sil_transform = global_transform.compute_transform();
+            *sil_global = *global_transform;
+        }
+
+        // Follow the mesh (or its bounds) the source currently displays
+        if reshape || mesh_changed || aabb.as_ref().is_some_and(Ref::is_changed) {
+            apply_silhouette_shape(
+                &mut commands.entity(silhouette),
+                &mut meshes,
+                shape,
+                (&mesh, aabb.as_deref()),
+                (sil_mesh, bounds),
+            );
+        }
+
+        // Follow the LOD range, skin and culling of the source
+        let mirror_changed = range.as_ref().is_some_and(Ref::is_changed)
+            || skin.as_ref().is_some_and(Ref::is_changed)
+            || no_culling.as_ref().is_some_and(Ref::is_added)
+            || removed_mirrored.contains(&entity);
+        if mirror_changed {
+            mirror_silhouette_source(&mut commands.entity(silhouette), mirrored);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selected_entities_get_silhouettes() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<OutlineSelection>()
+            .init_resource::<OutlineObjectIds>()
+            .init_resource::<OutlineRenderLayer>()
+            .insert_resource(SilhouetteWhiteMaterial(Handle::default()))
+            .add_systems(Update, sync_selection_meshes);
+        // Silhouettes are only spawned while a camera renders them
+        app.world_mut().spawn(OutlineSettings::default());
+        let entity = app
+            .world_mut()
+            .spawn((Mesh3d(Handle::default()), GlobalTransform::default()))
+            .id();
+        app.world_mut()
+            .resource_mut::<OutlineSelection>()
+            .entities
+            .insert(entity);
+
+        app.update();
+
+        let silhouette = app
+            .world()
+            .resource::<OutlineSelection>()
+            .silhouette(entity)
+            .expect("selected entity has no silhouette");
+        assert_eq!(
+            app.world().get::<SilhouetteMesh>(silhouette).unwrap().source,
+            entity
+        );
+
+        // Moving the entity moves its silhouette
+        let moved = GlobalTransform::from_translation(Vec3::X);
+        app.world_mut().entity_mut(entity).insert(moved);
+        app.update();
+        assert_eq!(app.world().get::<GlobalTransform>(silhouette), Some(&moved));
+
+        // Deselecting despawns it
+        app.world_mut()
+            .resource_mut::<OutlineSelection>()
+            .entities
+            .clear();
+        app.update();
+        assert!(app.world().get_entity(silhouette).is_err());
+        assert!(app
+            .world()
+            .resource::<OutlineSelection>()
+            .silhouette(entity)
+            .is_none());
+    }
+}
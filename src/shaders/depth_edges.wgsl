@@ -0,0 +1,101 @@
+// Depth Edge Shader
+// Detects depth and normal discontinuities from the prepass textures
+// and composites the edges over the scene
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var scene_texture: texture_2d<f32>;
+@group(0) @binding(1) var scene_sampler: sampler;
+@group(0) @binding(2) var depth_texture: texture_depth_2d;
+@group(0) @binding(3) var normal_texture: texture_2d<f32>;
+
+struct DepthEdgeSettings {
+    color: vec4<f32>,
+    width: f32,
+    depth_threshold: f32,
+    normal_threshold: f32,
+    perspective_near: f32,
+    // Accumulate coverage into the alpha with the premultiplied "over" operator
+    premultiplied_alpha: f32,
+    _padding1: f32,
+    _padding2: f32,
+    _padding3: f32,
+};
+
+@group(0) @binding(4) var<uniform> settings: DepthEdgeSettings;
+
+// Linear depth from a reverse-z depth buffer value
+fn linear_depth(depth: f32) -> f32 {
+    if settings.perspective_near > 0.0 {
+        return settings.perspective_near / max(depth, 1e-7);
+    }
+    // Orthographic depth is already linear (1 at near, 0 at far)
+    return 1.0 - depth;
+}
+
+fn load_depth(coord: vec2<i32>, size: vec2<i32>) -> f32 {
+    return textureLoad(depth_texture, clamp(coord, vec2<i32>(0), size - 1), 0);
+}
+
+fn load_normal(coord: vec2<i32>, size: vec2<i32>) -> vec3<f32> {
+    let encoded = textureLoad(normal_texture, clamp(coord, vec2<i32>(0), size - 1), 0).xyz;
+    return normalize(encoded * 2.0 - 1.0);
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let scene_color = textureSample(scene_texture, scene_sampler, in.uv);
+
+    // Width is zeroed when outlines are disabled
+    if settings.width <= 0.0 {
+        return scene_color;
+    }
+
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    let coord = vec2<i32>(in.position.xy);
+    let offset = max(i32(round(settings.width)), 1);
+
+    let center_raw_depth = load_depth(coord, size);
+    let center_depth = linear_depth(center_raw_depth);
+    let center_normal = load_normal(coord, size);
+
+    let offsets = array<vec2<i32>, 4>(
+        vec2<i32>(offset, 0),
+        vec2<i32>(-offset, 0),
+        vec2<i32>(0, offset),
+        vec2<i32>(0, -offset),
+    );
+
+    var edge = false;
+    for (var i = 0; i < 4; i++) {
+        let sample_coord = coord + offsets[i];
+        let sample_raw_depth = load_depth(sample_coord, size);
+
+        // Depth discontinuity - only the nearer side draws the edge
+        let depth_delta = (linear_depth(sample_raw_depth) - center_depth) / center_depth;
+        if center_raw_depth > 0.0 && depth_delta > settings.depth_threshold {
+            edge = true;
+        }
+
+        // Crease - normals are only meaningful where both pixels hit geometry
+        if center_raw_depth > 0.0 && sample_raw_depth > 0.0 {
+            let normal_delta = 1.0 - dot(center_normal, load_normal(sample_coord, size));
+            if normal_delta > settings.normal_threshold {
+                edge = true;
+            }
+        }
+    }
+
+    if !edge {
+        return scene_color;
+    }
+    let coverage = settings.color.a;
+    if settings.premultiplied_alpha < 0.5 {
+        return mix(scene_color, settings.color, coverage);
+    }
+    // Premultiplied "over", so captures with a transparent background keep proper alpha
+    return vec4<f32>(
+        settings.color.rgb * coverage + scene_color.rgb * (1.0 - coverage),
+        coverage + scene_color.a * (1.0 - coverage),
+    );
+}
@@ -0,0 +1,32 @@
+// Hover Pick Compute Shader
+// Reads the object ID of the silhouette texel under the cursor
+
+struct HoverPick {
+    // Silhouette texel under the cursor, u32::MAX when off-screen
+    texel: vec2<u32>,
+    // Picked object ID, written by this shader
+    object_id: u32,
+    _padding: u32,
+}
+
+@group(0) @binding(0) var silhouette_texture: texture_2d<f32>;
+@group(0) @binding(1) var<storage, read_write> pick: HoverPick;
+
+const NO_OBJECT: u32 = 0xFFFFFFFFu;
+
+@compute @workgroup_size(1, 1, 1)
+fn main() {
+    let tex_size = textureDimensions(silhouette_texture);
+
+    if pick.texel.x >= tex_size.x || pick.texel.y >= tex_size.y {
+        pick.object_id = NO_OBJECT;
+        return;
+    }
+
+    let silhouette = textureLoad(silhouette_texture, vec2<i32>(pick.texel), 0);
+    if silhouette.a > 0.5 {
+        pick.object_id = u32(silhouette.g) | (u32(silhouette.b) << 8u);
+    } else {
+        pick.object_id = NO_OBJECT;
+    }
+}
@@ -12,43 +12,500 @@
 
 struct OutlineSettings {
     color: vec4<f32>,
-    width: f32,
+    // Min and max UV of the region outlines are confined to
+    region: vec4<f32>,
+    // Horizontal and vertical outline width in pixels
+    width: vec2<f32>,
     enabled: f32,
-    _padding: vec2<f32>,
+    auto_contrast: f32,
+    near_fade_distance: f32,
+    dither_opacity: f32,
+    overscan: f32,
+    // Elapsed seconds, wrapped, to scroll dashes
+    time: f32,
+    temporal_smoothing: f32,
+    // View height in pixels and DPI scale factor, to convert object widths
+    view_height: f32,
+    scale_factor: f32,
+    // Whether any object has an interior fill
+    fill: f32,
+    preserve_alpha: f32,
+    // Accumulate coverage into the alpha with the premultiplied "over" operator
+    premultiplied_alpha: f32,
+    // Pixels per world unit, at unit depth for perspective projections
+    size_scale: f32,
+    // Near plane of a perspective projection, 0 for orthographic
+    perspective_near: f32,
+    // Far minus near plane of an orthographic projection
+    orthographic_depth: f32,
+    // Scan the silhouette for seeds instead of reading the Jump Flood result
+    dilate: f32,
+    // Draw onto a transparent texture instead of over the scene
+    detached: f32,
+    _padding1: f32,
 };
 
 @group(0) @binding(6) var<uniform> settings: OutlineSettings;
+#ifdef TEMPORAL_SMOOTHING
+@group(0) @binding(7) var history_texture: texture_2d<f32>;
+#endif
+struct ObjectStyle {
+    color: vec4<f32>,
+    fill: vec4<f32>,
+    vertex_colors: f32,
+    hatch_spacing: f32,
+    hatch_angle: f32,
+    hatch_width: f32,
+    // World-space bounding sphere radius, 0 if unknown
+    radius: f32,
+    // Fade the outline out from the silhouette edge behind nearer geometry
+    backlight: f32,
+    // Horizontal and vertical outline width in its unit
+    width: vec2<f32>,
+    // 0 for pixels, 1 for a viewport fraction, 2 for logical pixels
+    width_unit: f32,
+    // Only in the silhouette for hover picking: no outline and no fill
+    pick_only: f32,
+    // Dash and gap lengths in pixels along the contour, 0 gap for solid
+    dash_length: f32,
+    dash_gap: f32,
+    // Dash scroll speed in pixels per second
+    dash_speed: f32,
+    _padding1: f32,
+    _padding2: f32,
+    _padding3: f32,
+};
 
-@fragment
-fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    let scene_color = textureSample(scene_texture, scene_sampler, in.uv);
+// Outline style of each object, indexed by its silhouette object ID
+@group(0) @binding(8) var<storage, read> object_styles: array<ObjectStyle>;
 
-    if settings.enabled < 0.5 {
-        return scene_color;
+// Depth prepass of the view, or a far-plane placeholder without one
+@group(0) @binding(9) var depth_texture: texture_depth_2d;
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+#ifdef TEMPORAL_SMOOTHING
+    // Outline strength and object, read back next frame for temporal smoothing
+    @location(1) history: vec4<f32>,
+#endif
+};
+
+// Output `color`, and `strength` and `object_id` for the next frame while
+// temporal smoothing is on. The object ID is stored like in the silhouette,
+// with alpha marking whether there is one
+fn fragment_output(color: vec4<f32>, strength: f32, object_id: u32) -> FragmentOutput {
+#ifdef TEMPORAL_SMOOTHING
+    var history = vec4<f32>(strength, 0.0, 0.0, 0.0);
+    if object_id != NO_OBJECT {
+        history = vec4<f32>(strength, f32(object_id & 0xFFu), f32((object_id >> 8u) & 0xFFu), 1.0);
+    }
+    return FragmentOutput(color, history);
+#else
+    return FragmentOutput(color);
+#endif
+}
+
+// Strength difference to the previous frame beyond which history is
+// rejected, so fast moving outlines don't ghost
+const HISTORY_REJECT_MIN: f32 = 0.25;
+const HISTORY_REJECT_MAX: f32 = 0.75;
+
+// Draw `color` with `coverage` over `scene`. With premultiplied alpha the
+// "over" operator adds the coverage to the scene's alpha, so frames captured
+// with a transparent background composite correctly over black. Otherwise the
+// alpha is blended towards the color's like the color channels.
+fn blend_over(scene: vec4<f32>, color: vec4<f32>, coverage: f32) -> vec4<f32> {
+    if settings.premultiplied_alpha > 0.5 {
+        return vec4<f32>(
+            color.rgb * coverage + scene.rgb * (1.0 - coverage),
+            coverage + scene.a * (1.0 - coverage),
+        );
+    }
+    return mix(scene, color, coverage);
+}
+
+// Minimum perceptual luminance difference between outline and background
+// before auto contrast kicks in
+const MIN_CONTRAST: f32 = 0.4;
+
+// Approximate perceptual luminance of a linear color
+fn perceptual_luminance(color: vec3<f32>) -> f32 {
+    let linear = dot(saturate(color), vec3<f32>(0.2126, 0.7152, 0.0722));
+    return sqrt(linear);
+}
+
+// Push the outline color away from the background luminance, keeping its hue
+fn contrast_color(color: vec4<f32>, background: vec3<f32>) -> vec4<f32> {
+    let background_lum = perceptual_luminance(background);
+    let outline_lum = perceptual_luminance(color.rgb);
+    if abs(background_lum - outline_lum) >= MIN_CONTRAST {
+        return color;
+    }
+
+    // Dark outline over bright areas, light outline over dark areas
+    if background_lum > 0.5 {
+        return vec4<f32>(color.rgb * 0.2, color.a);
+    }
+    return vec4<f32>(mix(color.rgb, vec3<f32>(1.0), 0.75), color.a);
+}
+
+// 4x4 ordered dither (Bayer) thresholds in [0, 1)
+const BAYER_4X4: array<f32, 16> = array<f32, 16>(
+    0.0 / 16.0, 8.0 / 16.0, 2.0 / 16.0, 10.0 / 16.0,
+    12.0 / 16.0, 4.0 / 16.0, 14.0 / 16.0, 6.0 / 16.0,
+    3.0 / 16.0, 11.0 / 16.0, 1.0 / 16.0, 9.0 / 16.0,
+    15.0 / 16.0, 7.0 / 16.0, 13.0 / 16.0, 5.0 / 16.0,
+);
+
+// Dither threshold for a pixel, with the pattern phase shifted per object
+// so neighbouring outlines of different objects interleave
+fn dither_threshold(pixel: vec2<u32>, object_id: u32) -> f32 {
+    let shifted = pixel + vec2<u32>(object_id, object_id >> 2u);
+    return BAYER_4X4[(shifted.x % 4u) + (shifted.y % 4u) * 4u];
+}
+
+// Direction along the silhouette contour at a seed texel, turning the same
+// way around every contour. Taken from the coverage gradient around the seed,
+// which stays constant along straight edges, so the distance along an edge is
+// the seed's position projected onto it. Falls back to the direction from the
+// seed to the pixel, `to_pixel`, where the silhouette is too thin for one
+fn contour_tangent(seed: vec2<i32>, to_pixel: vec2<f32>) -> vec2<f32> {
+    let max_texel = vec2<i32>(textureDimensions(silhouette_texture)) - 1;
+    var inward = vec2<f32>(0.0);
+    for (var y = -1; y <= 1; y++) {
+        for (var x = -1; x <= 1; x++) {
+            let texel = clamp(seed + vec2<i32>(x, y), vec2<i32>(0), max_texel);
+            if is_outlined(textureLoad(silhouette_texture, texel, 0)) {
+                inward += vec2<f32>(f32(x), f32(y));
+            }
+        }
+    }
+    var normal = -inward;
+    if dot(normal, normal) < 0.5 {
+        normal = to_pixel;
     }
+    let outward = normalize(normal);
+    return vec2<f32>(-outward.y, outward.x);
+}
+
+const NO_OBJECT: u32 = 0xFFFFFFFFu;
+
+// Vertex color packed by the silhouette shader as 1 + RGB 4-4-3 bits of
+// its square root
+fn unpack_vertex_color(alpha: f32) -> vec3<f32> {
+    let packed = u32(max(alpha - 1.0, 0.0) + 0.5);
+    let encoded = vec3<f32>(
+        f32((packed >> 7u) & 0xFu) / 15.0,
+        f32((packed >> 3u) & 0xFu) / 15.0,
+        f32(packed & 0x7u) / 7.0,
+    );
+    return encoded * encoded;
+}
+
+// Object ID written into a silhouette texel by the silhouette shader
+fn silhouette_object_id(silhouette: vec4<f32>) -> u32 {
+    return u32(silhouette.g) | (u32(silhouette.b) << 8u);
+}
+
+// Whether a silhouette texel is covered by an outlined object. Objects only
+// drawn for hover picking don't count, so they seed no outline or fill and
+// don't hide the outlines of objects behind them
+fn is_outlined(silhouette: vec4<f32>) -> bool {
+    if silhouette.a <= 0.5 {
+        return false;
+    }
+    let object_id = silhouette_object_id(silhouette);
+    return object_id >= arrayLength(&object_styles) || object_styles[object_id].pick_only < 0.5;
+}
+
+struct OutlineSample {
+    // Strength of the outline, 0 outside the outline
+    strength: f32,
+    // Object the outline belongs to, NO_OBJECT outside the outline
+    object_id: u32,
+    // Vertex color of the nearest silhouette pixel
+    vertex_color: vec3<f32>,
+};
+
+fn no_outline() -> OutlineSample {
+    return OutlineSample(0.0, NO_OBJECT, vec3<f32>(0.0));
+}
+
+// Distance from the near plane of the scene geometry at this screen UV,
+// like the silhouette's red channel. Infinite for the background
+fn scene_distance(screen_uv: vec2<f32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    let depth = textureLoad(depth_texture, clamp(vec2<i32>(screen_uv * vec2<f32>(size)), vec2<i32>(0), size - 1), 0);
+    if depth <= 0.0 {
+        return 3.4e38;
+    }
+    // Reverse z: perspective depth is near / view depth, orthographic depth
+    // goes linearly from 1 at the near plane to 0 at the far plane
+    if settings.perspective_near > 0.0 {
+        return settings.perspective_near / depth - settings.perspective_near;
+    }
+    return (1.0 - depth) * settings.orthographic_depth;
+}
+
+// Map the screen UV into the outline textures, which extend past the view
+// by the overscan margin on each side
+fn outline_uv(screen_uv: vec2<f32>, tex_size: vec2<f32>) -> vec2<f32> {
+    return (screen_uv * (tex_size - 2.0 * settings.overscan) + settings.overscan) / tex_size;
+}
+
+// Interior tint of the object covering this pixel, transparent if none
+fn sample_fill(in: FullscreenVertexOutput) -> vec4<f32> {
+    let tex_size = vec2<f32>(textureDimensions(silhouette_texture));
+    let uv = outline_uv(in.uv, tex_size);
+    let silhouette = textureLoad(silhouette_texture, vec2<i32>(uv * tex_size), 0);
+    if !is_outlined(silhouette) {
+        return vec4<f32>(0.0);
+    }
+
+    let object_id = silhouette_object_id(silhouette);
+    if object_id >= arrayLength(&object_styles) {
+        return vec4<f32>(0.0);
+    }
+    let style = object_styles[object_id];
+    if style.hatch_spacing <= 0.0 {
+        return style.fill;
+    }
+
+    // Screen-space distance to the nearest hatch line, antialiased over a pixel.
+    // Screen y points down, so this normal turns the lines counterclockwise
+    let normal = vec2<f32>(sin(style.hatch_angle), cos(style.hatch_angle));
+    let offset = dot(in.position.xy, normal);
+    let line_distance = abs(offset - round(offset / style.hatch_spacing) * style.hatch_spacing);
+    let coverage = clamp(style.hatch_width * 0.5 - line_distance + 0.5, 0.0, 1.0);
+    return vec4<f32>(style.fill.rgb, style.fill.a * coverage);
+}
+
+// On-screen radius in pixels of an object's bounds, unbounded if unknown
+fn object_screen_radius(object_id: u32, near_distance: f32) -> f32 {
+    if settings.size_scale <= 0.0 || object_id >= arrayLength(&object_styles) {
+        return 1e9;
+    }
+    let radius = object_styles[object_id].radius;
+    if radius <= 0.0 {
+        return 1e9;
+    }
+    var depth = 1.0;
+    if settings.perspective_near > 0.0 {
+        depth = near_distance + settings.perspective_near;
+    }
+    return max(radius * settings.size_scale / depth, 1.0);
+}
+
+// Outline width of an object in pixels, no wider than the flood reaches
+fn object_width(object_id: u32) -> vec2<f32> {
+    if object_id >= arrayLength(&object_styles) {
+        return settings.width;
+    }
+    let style = object_styles[object_id];
+    var width = style.width;
+    if style.width_unit == 1.0 {
+        width *= settings.view_height;
+    } else if style.width_unit == 2.0 {
+        // Rounded half away from zero like the CPU side
+        width = floor(width * settings.scale_factor + 0.5);
+    }
+    return min(width, settings.width);
+}
+
+// UV of the pixel center a Jump Flood seed points to, or (0, 0) for none.
+// Seeds store their texel + 1 in 1/65535 steps
+fn decode_seed(seed: vec2<f32>, tex_size: vec2<f32>) -> vec2<f32> {
+    if seed.x <= 0.0 {
+        return vec2<f32>(0.0);
+    }
+    return (round(seed * 65535.0) - 0.5) / tex_size;
+}
+
+// Nearest covered silhouette pixel within the outline width, found by
+// scanning the disk of pixels the width reaches directly instead of running
+// the Jump Flood, see THIN_OUTLINE_WIDTH. Returned like decode_seed: the UV
+// of the pixel center, or (0, 0)
+fn dilate_seed(uv: vec2<f32>, tex_size: vec2<f32>) -> vec2<f32> {
+    let center = vec2<i32>(uv * tex_size);
+    let max_texel = vec2<i32>(tex_size) - 1;
+    let max_width = max(settings.width.x, settings.width.y);
+    let radius = i32(ceil(max_width));
+    // Pixels further away are never within reach
+    var best = max_width * max_width + 0.5;
+    var seed = vec2<f32>(0.0);
+    for (var y = -radius; y <= radius; y++) {
+        for (var x = -radius; x <= radius; x++) {
+            let texel = center + vec2<i32>(x, y);
+            if any(texel < vec2<i32>(0)) || any(texel > max_texel) {
+                continue;
+            }
+            let distance = f32(x * x + y * y);
+            if distance < best && is_outlined(textureLoad(silhouette_texture, texel, 0)) {
+                best = distance;
+                seed = (vec2<f32>(texel) + 0.5) / tex_size;
+            }
+        }
+    }
+    return seed;
+}
+
+// Outline at this pixel and the object it belongs to
+fn sample_outline(in: FullscreenVertexOutput) -> OutlineSample {
+    let tex_size = vec2<f32>(textureDimensions(jfa_texture));
+    let uv = outline_uv(in.uv, tex_size);
 
     // Check JFA first - most pixels have no valid seed (cheaper than silhouette sample)
     // With rg16unorm format, invalid seeds are (0, 0) since valid UVs are at pixel centers
-    let seed_uv = textureSample(jfa_texture, jfa_sampler, in.uv).xy;
+    var seed_uv: vec2<f32>;
+    if settings.dilate > 0.5 {
+        seed_uv = dilate_seed(uv, tex_size);
+    } else {
+        seed_uv = decode_seed(textureSample(jfa_texture, jfa_sampler, uv).xy, tex_size);
+    }
     if seed_uv.x <= 0.0 {
-        return scene_color;
+        return no_outline();
     }
 
+    // Silhouette data of the seed: r = distance from near plane, g/b = object ID
+    let seed_data = textureLoad(silhouette_texture, vec2<i32>(seed_uv * tex_size), 0);
+    let object_id = silhouette_object_id(seed_data);
+
     // Calculate distance and early-out if beyond outline width
-    let tex_size = vec2<f32>(textureDimensions(jfa_texture));
-    let diff = (in.uv - seed_uv) * tex_size;
+    let diff = (uv - seed_uv) * tex_size;
     let dist = length(diff);
-    if dist > settings.width {
-        return scene_color;
+    // Width towards the seed, on the ellipse spanned by the horizontal and
+    // vertical widths. Tiny objects get an outline no wider than themselves
+    // instead of a blob many times their size
+    let width = min(object_width(object_id), vec2<f32>(object_screen_radius(object_id, seed_data.r)));
+    let reach = dist / max(length(diff / max(width, vec2<f32>(1e-4))), 1e-6);
+    if dist > reach {
+        return no_outline();
     }
 
-    // Only sample silhouette for pixels potentially in the outline
-    let silhouette = textureSample(silhouette_texture, silhouette_sampler, in.uv).a;
-    if silhouette > 0.5 {
-        return scene_color;
+    // Dashes along the contour, scrolled over time for marching ants
+    if object_id < arrayLength(&object_styles) && object_styles[object_id].dash_gap > 0.0 {
+        let style = object_styles[object_id];
+        let seed_texel = seed_uv * tex_size;
+        let period = style.dash_length + style.dash_gap;
+        let position = dot(seed_texel, contour_tangent(vec2<i32>(seed_texel), diff));
+        let phase = position - settings.time * style.dash_speed;
+        if phase - floor(phase / period) * period >= style.dash_length {
+            return no_outline();
+        }
+    }
+
+    // Only read the silhouette for pixels potentially in the outline. Its
+    // alpha carries packed vertex colors, so it must not be filtered
+    let silhouette = textureLoad(silhouette_texture, vec2<i32>(uv * tex_size), 0);
+    if is_outlined(silhouette) {
+        return no_outline();
     }
 
     // Smooth outline with 1-pixel AA falloff
-    let outline_strength = 1.0 - smoothstep(settings.width - 1.0, settings.width, dist);
-    return mix(scene_color, settings.color, outline_strength * settings.color.a);
+    var outline_strength = 1.0 - smoothstep(reach - 1.0, reach, dist);
+
+    // Backlit objects get a soft halo, brightest against their edge and
+    // hidden by geometry in front of the edge it comes from
+    if object_id < arrayLength(&object_styles) && object_styles[object_id].backlight > 0.5 {
+        if scene_distance(in.uv) < seed_data.r * 0.99 {
+            return no_outline();
+        }
+        let falloff = 1.0 - saturate(dist / max(reach, 1e-4));
+        outline_strength = falloff * falloff;
+    }
+
+    // Fade outlines drawn from seeds near the camera near plane
+    if settings.near_fade_distance > 0.0 {
+        outline_strength *= smoothstep(0.0, settings.near_fade_distance, seed_data.r);
+    }
+
+    // Ordered dither so overlapping outlines stay individually legible
+    if settings.dither_opacity >= 0.0 {
+        if dither_threshold(vec2<u32>(in.position.xy), object_id) >= settings.dither_opacity {
+            return no_outline();
+        }
+    }
+    return OutlineSample(outline_strength, object_id, unpack_vertex_color(seed_data.a));
+}
+
+// What outlines are drawn over: the scene, or transparent black when the
+// user composites them
+fn composite_base(scene: vec4<f32>) -> vec4<f32> {
+    if settings.detached > 0.5 {
+        return vec4<f32>(0.0);
+    }
+    return scene;
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> FragmentOutput {
+    let scene = textureSample(scene_texture, scene_sampler, in.uv);
+    var scene_color = composite_base(scene);
+
+    if settings.enabled < 0.5 {
+        return fragment_output(scene_color, 0.0, NO_OBJECT);
+    }
+
+    // Cut outlines off outside the configured region, e.g. over a HUD
+    if any(in.uv < settings.region.xy) || any(in.uv >= settings.region.zw) {
+        return fragment_output(scene_color, 0.0, NO_OBJECT);
+    }
+
+    let scene_alpha = scene_color.a;
+
+    // Tint object interiors, skipping the silhouette read when nothing is filled
+    if settings.fill > 0.5 {
+        let fill = sample_fill(in);
+        scene_color = blend_over(scene_color, vec4<f32>(fill.rgb, scene_color.a), fill.a);
+        if settings.preserve_alpha > 0.5 {
+            scene_color.a = scene_alpha;
+        }
+    }
+
+    let outline = sample_outline(in);
+    var strength = outline.strength;
+    var object_id = outline.object_id;
+
+#ifdef TEMPORAL_SMOOTHING
+    // Blend with last frame's strength, backing off where it changed a lot.
+    // Smoothing is 0 while the history doesn't hold last frame's outlines.
+    // Loaded unfiltered, as it also holds last frame's object ID
+    if settings.temporal_smoothing > 0.0 {
+        let history_size = vec2<f32>(textureDimensions(history_texture));
+        let history = textureLoad(history_texture, vec2<i32>(in.uv * history_size), 0);
+        let rejection = smoothstep(HISTORY_REJECT_MIN, HISTORY_REJECT_MAX, abs(history.r - strength));
+        strength = mix(strength, history.r, settings.temporal_smoothing * (1.0 - rejection));
+        // Pixels only kept by history keep the object they outlined last frame
+        if object_id == NO_OBJECT && history.a > 0.5 {
+            object_id = silhouette_object_id(history);
+        }
+    }
+#endif
+
+    if strength <= 0.0 {
+        return fragment_output(scene_color, strength, NO_OBJECT);
+    }
+
+    var outline_color = settings.color;
+    if object_id < arrayLength(&object_styles) {
+        let style = object_styles[object_id];
+        outline_color = style.color;
+        // Pixels only kept by history have no vertex color of their own
+        if style.vertex_colors > 0.5 && object_id == outline.object_id {
+            outline_color = vec4<f32>(outline.vertex_color, style.color.a);
+        }
+    }
+    if settings.auto_contrast > 0.5 {
+        // Contrast with what ends up behind the outline once composited
+        var background = scene_color.rgb;
+        if settings.detached > 0.5 {
+            background += scene.rgb * (1.0 - scene_color.a);
+        }
+        outline_color = contrast_color(outline_color, background);
+    }
+    var color = blend_over(scene_color, outline_color, strength * outline_color.a);
+    if settings.preserve_alpha > 0.5 {
+        color.a = scene_alpha;
+    }
+    return fragment_output(color, strength, object_id);
 }
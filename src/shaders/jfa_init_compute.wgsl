@@ -5,8 +5,31 @@
 @group(0) @binding(0) var silhouette_texture: texture_2d<f32>;
 @group(0) @binding(1) var output_texture: texture_storage_2d<rg16unorm, write>;
 
-// Invalid seed marker - 0.0 works since valid UVs are at pixel centers (always > 0)
-// With rg16unorm format, negative values clamp to 0.0
+// Laid out like the composite shader's ObjectStyle, only pick_only is read
+struct ObjectStyle {
+    color: vec4<f32>,
+    fill: vec4<f32>,
+    vertex_colors: f32,
+    hatch_spacing: f32,
+    hatch_angle: f32,
+    hatch_width: f32,
+    radius: f32,
+    backlight: f32,
+    width: vec2<f32>,
+    width_unit: f32,
+    // Object only in the silhouette for hover picking, never a seed
+    pick_only: f32,
+    dash_length: f32,
+    dash_gap: f32,
+    dash_speed: f32,
+    _padding1: f32,
+    _padding2: f32,
+    _padding3: f32,
+};
+
+@group(0) @binding(2) var<storage, read> object_styles: array<ObjectStyle>;
+
+// Invalid seed marker - 0.0 works since valid seeds store their texel + 1 (always > 0)
 const INVALID_SEED: vec2<f32> = vec2<f32>(0.0, 0.0);
 
 @compute @workgroup_size(8, 8, 1)
@@ -19,14 +42,18 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
 
     let coord = vec2<i32>(global_id.xy);
-    let uv = (vec2<f32>(global_id.xy) + 0.5) / vec2<f32>(tex_size);
 
-    let alpha = textureLoad(silhouette_texture, coord, 0).a;
+    let silhouette = textureLoad(silhouette_texture, coord, 0);
+    let object_id = u32(silhouette.g) | (u32(silhouette.b) << 8u);
+    let pick_only = object_id < arrayLength(&object_styles)
+        && object_styles[object_id].pick_only > 0.5;
 
-    // If inside object (silhouette), this pixel is a seed - store its UV
+    // If inside an outlined object (silhouette), this pixel is a seed - store
+    // its texel + 1 in 1/65535 steps, exact in rg16unorm at any resolution.
     // Otherwise, store invalid marker
-    if alpha > 0.5 {
-        textureStore(output_texture, coord, vec4<f32>(uv, 0.0, 0.0));
+    if silhouette.a > 0.5 && !pick_only {
+        let seed = (vec2<f32>(global_id.xy) + 1.0) / 65535.0;
+        textureStore(output_texture, coord, vec4<f32>(seed, 0.0, 0.0));
     } else {
         textureStore(output_texture, coord, vec4<f32>(INVALID_SEED, 0.0, 0.0));
     }
@@ -14,17 +14,17 @@ struct JfaParams {
 
 @group(0) @binding(2) var<uniform> params: JfaParams;
 
-// Invalid seed marker - 0.0 works since valid UVs are at pixel centers (always > 0)
+// Invalid seed marker - 0.0 works since valid seeds store their texel + 1 (always > 0)
 const INVALID_SEED: vec2<f32> = vec2<f32>(0.0, 0.0);
 
-// Check if a seed coordinate is valid (valid UVs are at pixel centers, always > 0)
+// Check if a seed coordinate is valid (valid seeds store their texel + 1, always > 0)
 fn is_valid_seed(seed: vec2<f32>) -> bool {
     return seed.x > 0.0;
 }
 
-// Calculate squared distance from pixel UV to seed UV (in pixel space)
-fn seed_distance_sq(pixel_uv: vec2<f32>, seed_uv: vec2<f32>, tex_size: vec2<f32>) -> f32 {
-    let diff = (pixel_uv - seed_uv) * tex_size;
+// Calculate squared distance from a pixel to a seed (in pixel space)
+fn seed_distance_sq(pixel: vec2<i32>, seed: vec2<f32>) -> f32 {
+    let diff = vec2<f32>(pixel) + 1.0 - round(seed * 65535.0);
     return dot(diff, diff);
 }
 
@@ -39,7 +39,6 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
 
     let coord = vec2<i32>(global_id.xy);
-    let uv = (vec2<f32>(global_id.xy) + 0.5) / tex_size;
 
     let step_size = i32(params.step_size);
 
@@ -60,7 +59,7 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             let seed = textureLoad(input_texture, sample_coord, 0).xy;
 
             if is_valid_seed(seed) {
-                let dist_sq = seed_distance_sq(uv, seed, tex_size);
+                let dist_sq = seed_distance_sq(coord, seed);
                 if dist_sq < best_dist_sq {
                     best_dist_sq = dist_sq;
                     best_seed = seed;
@@ -1,9 +1,61 @@
-// Minimal silhouette shader - outputs solid white
-// No lighting, no textures, no PBR - just white fragments
+// Minimal silhouette shader - outputs solid coverage
+// No lighting, no textures, no PBR
+// Red channel stores the distance from the near plane, used to fade
+// the outline of objects clipped by the camera
+// Green/blue channels store the object ID (low/high byte) from the mesh tag
+// Alpha-cutout sources discard the same fragments as their material
+// Alpha stores 1 + the vertex color packed as RGB 4-4-3 bits of its square
+// root, small enough to stay exact in the f16 texture
 
-#import bevy_pbr::forward_io::VertexOutput
+#import bevy_pbr::{
+    forward_io::VertexOutput,
+    mesh_functions::get_tag,
+    mesh_view_bindings::view,
+}
+
+struct SilhouetteMaterial {
+    uv_transform: mat3x3<f32>,
+    alpha_cutoff: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> material: SilhouetteMaterial;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var alpha_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var alpha_sampler: sampler;
+
+// Near plane distance along the view direction for the current projection
+fn near_plane() -> f32 {
+    // Orthographic: clip_z = z_view / d + far / d, near maps to clip_z = 1
+    if view.clip_from_view[3][3] == 1.0 {
+        return (view.clip_from_view[3][2] - 1.0) / view.clip_from_view[2][2];
+    }
+    // Perspective (infinite reverse z)
+    return view.clip_from_view[3][2];
+}
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    return vec4<f32>(1.0, 1.0, 1.0, 1.0);
+#ifdef VERTEX_UVS_A
+    let uv = (material.uv_transform * vec3<f32>(in.uv, 1.0)).xy;
+    if textureSample(alpha_texture, alpha_sampler, uv).a < material.alpha_cutoff {
+        discard;
+    }
+#endif
+
+    var coverage = 1.0;
+#ifdef VERTEX_COLORS
+    let encoded = sqrt(saturate(in.color.rgb));
+    let packed = (u32(round(encoded.r * 15.0)) << 7u)
+        | (u32(round(encoded.g * 15.0)) << 3u)
+        | u32(round(encoded.b * 7.0));
+    coverage += f32(packed);
+#endif
+
+    let view_depth = -(view.view_from_world * in.world_position).z;
+    let object_id = get_tag(in.instance_index);
+    return vec4<f32>(
+        max(view_depth - near_plane(), 0.0),
+        f32(object_id & 0xFFu),
+        f32((object_id >> 8u) & 0xFFu),
+        coverage,
+    );
 }
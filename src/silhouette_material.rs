@@ -1,24 +1,174 @@
 //! Minimal material for silhouette rendering.
 //!
-//! This material outputs solid white with no lighting calculations,
-//! replacing the heavyweight PBR shader for silhouette passes.
+//! This material outputs per-object silhouette data with no lighting
+//! calculations, replacing the heavyweight PBR shader for silhouette passes.
+//!
+//! Only the fragment shader is custom. Vertices go through Bevy's standard
+//! mesh vertex shader, specialized from the mesh layout alone, so indexed
+//! and non-indexed meshes produce identical silhouettes. Sources with an
+//! [`OutlineVertexShader`](crate::OutlineVertexShader) replace it with their
+//! own, and sources with an
+//! [`OutlineFragmentShader`](crate::OutlineFragmentShader) the fragment shader.
 
 use bevy::{
+    mesh::MeshVertexBufferLayoutRef,
+    pbr::{MaterialPipeline, MaterialPipelineKey, OpaqueRendererMethod},
     prelude::*,
-    render::render_resource::AsBindGroup,
-    shader::ShaderRef,
+    render::render_resource::{
+        AsBindGroup, Face, RenderPipelineDescriptor, SpecializedMeshPipelineError,
+    },
+    shader::{Shader, ShaderRef},
 };
 
-/// A minimal material that outputs solid white.
-/// Used for silhouette rendering where we only need object presence.
-#[derive(Asset, TypePath, AsBindGroup, Clone, Default)]
+/// A minimal material that outputs silhouette data instead of shading: the
+/// distance from the near plane, the object ID, and coverage with the packed
+/// vertex color, see [`OutlineCameraLink::silhouette_texture`](crate::OutlineCameraLink::silhouette_texture).
+///
+/// When the source mesh is alpha-cutout, the silhouette samples the same
+/// texture alpha at the same UVs so the outline follows the visible shape.
+/// It also culls the same faces as the source, so double-sided sources keep
+/// full coverage.
+#[derive(Asset, TypePath, AsBindGroup, Clone, PartialEq)]
+#[bind_group_data(SilhouetteMaterialKey)]
 pub struct SilhouetteMaterial {
+    /// UV transform of the source material, e.g. the current sprite-sheet frame
+    #[uniform(0)]
+    pub uv_transform: Mat3,
+    /// Fragments with alpha below this value are discarded
     #[uniform(0)]
-    _dummy: u32,
+    pub alpha_cutoff: f32,
+    /// Texture whose alpha masks the silhouette, white when unset
+    #[texture(1)]
+    #[sampler(2)]
+    pub alpha_texture: Option<Handle<Image>>,
+    /// Faces culled when drawing the silhouette, matching the source material
+    pub cull_mode: Option<Face>,
+    /// Vertex shader replacing Bevy's mesh vertex shader, see
+    /// [`OutlineVertexShader`](crate::OutlineVertexShader)
+    pub vertex_shader: Option<Handle<Shader>>,
+    /// Texture available to [`vertex_shader`](Self::vertex_shader), white
+    /// when unset
+    #[texture(3, visibility(vertex))]
+    #[sampler(4, visibility(vertex))]
+    pub vertex_texture: Option<Handle<Image>>,
+    /// Fragment shader replacing the built-in silhouette shader, see
+    /// [`OutlineFragmentShader`](crate::OutlineFragmentShader)
+    pub fragment_shader: Option<Handle<Shader>>,
+}
+
+impl Default for SilhouetteMaterial {
+    fn default() -> Self {
+        Self {
+            uv_transform: Mat3::IDENTITY,
+            alpha_cutoff: 0.0,
+            alpha_texture: None,
+            cull_mode: Some(Face::Back),
+            vertex_shader: None,
+            vertex_texture: None,
+            fragment_shader: None,
+        }
+    }
+}
+
+impl SilhouetteMaterial {
+    /// Builds a silhouette matching `material`'s alpha cutout and face
+    /// culling, or `None` if the default silhouette already matches it.
+    pub fn matching(material: &StandardMaterial) -> Option<Self> {
+        let mask = Self::alpha_mask(material);
+        if mask.is_none() && material.cull_mode == Some(Face::Back) {
+            return None;
+        }
+        Some(Self {
+            cull_mode: material.cull_mode,
+            ..mask.unwrap_or_default()
+        })
+    }
+
+    /// Whether `material` draws nothing at all, like the invisible collision
+    /// proxies some glTF exports contain. Such sources get no silhouette.
+    pub fn is_invisible(material: &StandardMaterial) -> bool {
+        let transparent = matches!(
+            material.alpha_mode,
+            AlphaMode::Mask(_)
+                | AlphaMode::Blend
+                | AlphaMode::Premultiplied
+                | AlphaMode::AlphaToCoverage
+        );
+        transparent && material.base_color.alpha() <= 0.0
+    }
+
+    /// Builds an alpha-masked silhouette matching `material`, or `None` if
+    /// the source material is opaque or untextured.
+    pub fn alpha_mask(material: &StandardMaterial) -> Option<Self> {
+        let alpha_cutoff = match material.alpha_mode {
+            AlphaMode::Mask(cutoff) => cutoff,
+            AlphaMode::Blend | AlphaMode::Premultiplied | AlphaMode::AlphaToCoverage => 0.5,
+            AlphaMode::Opaque | AlphaMode::Add | AlphaMode::Multiply => return None,
+        };
+        Some(Self {
+            uv_transform: material.uv_transform.into(),
+            alpha_cutoff,
+            alpha_texture: Some(material.base_color_texture.clone()?),
+            ..default()
+        })
+    }
+}
+
+/// Pipeline key of a [`SilhouetteMaterial`]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SilhouetteMaterialKey {
+    /// 0 culls nothing, 1 front faces, 2 back faces
+    cull_mode: u32,
+    vertex_shader: Option<Handle<Shader>>,
+    fragment_shader: Option<Handle<Shader>>,
+}
+
+impl From<&SilhouetteMaterial> for SilhouetteMaterialKey {
+    fn from(material: &SilhouetteMaterial) -> Self {
+        let cull_mode = match material.cull_mode {
+            None => 0,
+            Some(Face::Front) => 1,
+            Some(Face::Back) => 2,
+        };
+        Self {
+            cull_mode,
+            vertex_shader: material.vertex_shader.clone(),
+            fragment_shader: material.fragment_shader.clone(),
+        }
+    }
 }
 
 impl Material for SilhouetteMaterial {
     fn fragment_shader() -> ShaderRef {
         "embedded://bevy_outliner/shaders/silhouette.wgsl".into()
     }
+
+    // Silhouette cameras have no deferred prepass, so silhouettes must stay
+    // forward even when `DefaultOpaqueRendererMethod` is deferred
+    fn opaque_render_method(&self) -> OpaqueRendererMethod {
+        OpaqueRendererMethod::Forward
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        descriptor.primitive.cull_mode = match key.bind_group_data.cull_mode {
+            0 => None,
+            1 => Some(Face::Front),
+            _ => Some(Face::Back),
+        };
+        if let Some(shader) = &key.bind_group_data.vertex_shader {
+            descriptor.vertex.shader = shader.clone();
+        }
+        if let (Some(shader), Some(fragment)) = (
+            &key.bind_group_data.fragment_shader,
+            descriptor.fragment.as_mut(),
+        ) {
+            fragment.shader = shader.clone();
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,147 @@
+//! Value-driven outlines.
+//!
+//! [`OutlineValue`] maps a normalized gameplay value, such as health, to the
+//! color and width of an entity's [`MeshOutline`], so the outline reflects
+//! the value as it changes.
+
+use bevy::prelude::*;
+
+use crate::components::MeshOutline;
+
+/// Tints and sizes an entity's [`MeshOutline`] from a value between `0` and
+/// `1`, such as its health divided by its maximum health.
+///
+/// The outline color runs from [`low_color`](Self::low_color) at `0` to
+/// [`high_color`](Self::high_color) at `1`, and the width likewise from
+/// [`low_width`](Self::low_width) to [`high_width`](Self::high_width).
+/// Update [`value`](Self::value) from your own systems; the outline follows
+/// the same frame. The rest of the [`MeshOutline`] is left as is.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_outliner::prelude::*;
+/// let health = OutlineValue::new(LinearRgba::RED, LinearRgba::GREEN).widths(8.0, 3.0);
+/// let (color, width) = health.with_value(0.5).sample();
+/// assert_eq!(width, 5.5);
+/// assert_eq!(color, LinearRgba::new(0.5, 0.5, 0.0, 1.0));
+/// ```
+///
+/// Colors are interpolated in the color space of
+/// [`low_color`](Self::low_color). [`Oklcha`] endpoints give perceptually
+/// even hue transitions, without the muddy midpoint of linear RGB between
+/// e.g. red and green:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_outliner::prelude::*;
+/// let (red, green) = (Oklcha::lch(0.63, 0.26, 29.0), Oklcha::lch(0.87, 0.29, 142.0));
+/// let (midpoint, _) = OutlineValue::new(red, green).with_value(0.5).sample();
+/// let midpoint = Oklcha::from(midpoint);
+/// // The hue passes between the endpoints' hues
+/// assert!(midpoint.hue > 29.0 && midpoint.hue < 142.0);
+/// // And keeps the saturation linear RGB loses halfway
+/// let muddy = Oklcha::from(LinearRgba::from(red).mix(&LinearRgba::from(green), 0.5));
+/// assert!(midpoint.chroma > muddy.chroma + 0.05);
+/// ```
+#[derive(Component, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct OutlineValue {
+    /// Current value, clamped to `0..=1`.
+    pub value: f32,
+    /// Outline color at a value of `0`.
+    pub low_color: Color,
+    /// Outline color at a value of `1`.
+    pub high_color: Color,
+    /// Uniform outline width at a value of `0`, in the outline's width unit.
+    pub low_width: f32,
+    /// Uniform outline width at a value of `1`, in the outline's width unit.
+    pub high_width: f32,
+}
+
+impl OutlineValue {
+    /// Gradient from `low_color` to `high_color` at a constant default
+    /// width, starting at a value of `1`.
+    pub fn new(low_color: impl Into<Color>, high_color: impl Into<Color>) -> Self {
+        let width = MeshOutline::default().width.x;
+        Self {
+            value: 1.0,
+            low_color: low_color.into(),
+            high_color: high_color.into(),
+            low_width: width,
+            high_width: width,
+        }
+    }
+
+    /// Set the widths at a value of `0` and `1`.
+    pub fn widths(mut self, low_width: f32, high_width: f32) -> Self {
+        self.low_width = low_width;
+        self.high_width = high_width;
+        self
+    }
+
+    /// Set [`value`](Self::value).
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Outline color and width at the current [`value`](Self::value).
+    pub fn sample(&self) -> (LinearRgba, f32) {
+        let t = self.value.clamp(0.0, 1.0);
+        (
+            self.low_color.mix(&self.high_color, t).to_linear(),
+            self.low_width.lerp(self.high_width, t),
+        )
+    }
+}
+
+/// System to write the color and width of [`OutlineValue`]s to their outlines
+pub fn update_outline_value(
+    mut entities: Query<
+        (&OutlineValue, &mut MeshOutline),
+        Or<(Changed<OutlineValue>, Added<MeshOutline>)>,
+    >,
+) {
+    for (value, mut outline) in entities.iter_mut() {
+        let (color, width) = value.sample();
+        outline.color = color;
+        outline.width = Vec2::splat(width);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outline_follows_value() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, update_outline_value);
+        let entity = app
+            .world_mut()
+            .spawn((
+                OutlineValue::new(LinearRgba::RED, LinearRgba::GREEN)
+                    .widths(8.0, 3.0)
+                    .with_value(0.0),
+                MeshOutline::default(),
+            ))
+            .id();
+
+        app.update();
+
+        let outline = app.world().get::<MeshOutline>(entity).unwrap();
+        assert_eq!(outline.color, LinearRgba::RED);
+        assert_eq!(outline.width, Vec2::splat(8.0));
+
+        app.world_mut()
+            .get_mut::<OutlineValue>(entity)
+            .unwrap()
+            .value = 1.0;
+        app.update();
+
+        let outline = app.world().get::<MeshOutline>(entity).unwrap();
+        assert_eq!(outline.color, LinearRgba::GREEN);
+        assert_eq!(outline.width, Vec2::splat(3.0));
+    }
+}